@@ -1,2 +1 @@
-
-criterion::criterion_main!(aoclib::bench::year_2015, aoclib::bench::year_2022, aoclib::bench::year_2024);
\ No newline at end of file
+include!(concat!(env!("OUT_DIR"), "/bench_main.rs"));
\ No newline at end of file
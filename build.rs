@@ -0,0 +1,168 @@
+use std::{fs, path::Path};
+
+// Scans `src/year_*/day_*.rs` and generates the `aoc_solvers!` invocation for
+// `lib.rs` and the `criterion_main!` invocation for the benches, so adding a
+// day is just a matter of creating its file.
+
+fn sorted_entries(dir: &Path, prefix: &str, extension: Option<&str>) -> Vec<String> {
+    let mut names =
+        fs::read_dir(dir)
+            .unwrap_or_else(|error| panic!("Failed to read {:?}: {:?}", dir, error))
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != extension {
+                    return None;
+                }
+
+                let name = path.file_stem()?.to_str()?.to_string();
+                let number = name.strip_prefix(prefix)?;
+                if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+
+                Some(name)
+            })
+            .collect::<Vec<_>>();
+
+    names.sort();
+    names
+}
+
+fn has_part(source: &str, part: &str) -> bool {
+    ["pub fn ", "pub(crate) fn "]
+        .iter()
+        .any(|visibility| source.contains(&format!("{}{}(", visibility, part)))
+}
+
+/// Name of the type implementing `Solver` in a day, if the day uses one.
+fn find_solver(source: &str) -> Option<String> {
+    source.lines().find_map(|line| {
+        let line = line.trim_start().strip_prefix("impl ")?;
+        let (_, solver) = line.split_once("Solver for ")?;
+        let solver = solver.split(|c: char| !c.is_alphanumeric() && c != '_').next()?;
+
+        Some(solver.to_string()).filter(|solver| !solver.is_empty())
+    })
+}
+
+/// The `pub fn part1_<name>` and `pub fn part2_<name>` variants of a day, as
+/// (part, name, function) tuples.
+fn find_variants(source: &str) -> Vec<(usize, String, String)> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let function = line.strip_prefix("pub fn ").or_else(|| line.strip_prefix("pub(crate) fn "))?;
+            let function = function.split(|c: char| !c.is_alphanumeric() && c != '_').next()?;
+            let (part, name) = function.strip_prefix("part")?.split_once('_')?;
+            let part = part.parse::<usize>().ok().filter(|part| (1..=2).contains(part))?;
+
+            (name.starts_with(|c: char| c.is_ascii_alphabetic()) && name != "default").then(|| (part, name.to_string(), function.to_string()))
+        })
+        .collect()
+}
+
+/// Whether a day declares puzzle parameters.
+fn has_params(source: &str) -> bool {
+    source.lines().any(|line| line.trim_start().starts_with("pub const PARAMS:"))
+}
+
+fn main() {
+    let src_dir = Path::new("src");
+    println!("cargo:rerun-if-changed=src");
+
+    let mut years = Vec::new();
+    let mut errors = Vec::new();
+    for year in sorted_entries(src_dir, "year_", None) {
+        let year_dir = src_dir.join(&year);
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        let mut days = Vec::new();
+        for day in sorted_entries(&year_dir, "day_", Some("rs")) {
+            let source = fs::read_to_string(year_dir.join(format!("{}.rs", day))).unwrap();
+            let variants = find_variants(&source);
+            let params = has_params(&source);
+            if let Some(solver) = find_solver(&source) {
+                days.push((day, Some(solver), params, variants));
+                continue;
+            }
+
+            let missing_parts =
+                ["part1", "part2"]
+                    .into_iter()
+                    .filter(|part| !has_part(&source, part))
+                    .collect::<Vec<_>>();
+
+            if missing_parts.is_empty() {
+                days.push((day, None, params, variants));
+
+            } else {
+                errors.push(format!("src/{}/{}.rs is missing `pub fn {}` or a `Solver` impl", year, day, missing_parts.join("` and `pub fn ")));
+            }
+        }
+
+        if !days.is_empty() {
+            years.push((year, days));
+        }
+    }
+
+    let mut solvers = String::new();
+    for error in errors {
+        solvers += &format!("compile_error!({:?});\n", error);
+    }
+
+    // `mod` items in an `include!`d file are resolved relative to the included
+    // file, so every day module gets an explicit path back into `src`.
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    solvers += "aoc_solvers!{\n";
+    solvers +=
+        &years
+            .iter()
+            .map(|(year, days)| {
+                let days =
+                    days.iter()
+                        .map(|(day, solver, params, variants)| {
+                            let path = Path::new(&manifest_dir).join("src").join(year).join(format!("{}.rs", day));
+                            let mut day = match solver {
+                                Some(solver) => format!("        #[path = {:?}] {}: {}", path.display().to_string(), day, solver),
+                                None => format!("        #[path = {:?}] {}", path.display().to_string(), day)
+                            };
+
+                            if *params {
+                                day += " (PARAMS)";
+                            }
+
+                            if !variants.is_empty() {
+                                let variants =
+                                    variants.iter()
+                                        .map(|(part, name, function)| format!("{} {} {}", part, name, function))
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+
+                                day += &format!(" [{}]", variants);
+                            }
+
+                            day
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",\n");
+
+                format!("    {} {{\n{}\n    }}", year, days)
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+    solvers += "\n}\n";
+
+    let bench_main = format!(
+        "criterion::criterion_main!({});\n",
+        years
+            .iter()
+            .map(|(year, _)| format!("aoclib::bench::{}", year))
+            .collect::<Vec<_>>()
+            .join(", "));
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solvers.rs"), solvers).unwrap();
+    fs::write(Path::new(&out_dir).join("bench_main.rs"), bench_main).unwrap();
+}
@@ -0,0 +1,120 @@
+use std::cell::Cell;
+
+// Allocation counting for `aocbin run --mem`. The counting allocator is only
+// installed with the `mem-stats` feature so regular runs and the criterion
+// benches keep using the system allocator directly.
+
+#[derive(Clone, Copy, Default)]
+pub struct MemStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub peak_bytes: u64
+}
+
+pub const ENABLED: bool = cfg!(feature = "mem-stats");
+
+// Counters are per thread so that parts running in parallel on other threads
+// don't show up in each other's numbers.
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATED_BYTES: Cell<u64> = const { Cell::new(0) };
+    static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+    static PEAK_LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+}
+
+#[cfg(feature = "mem-stats")]
+mod counting {
+
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::*;
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn record_allocation(size: usize) {
+        // `try_with` since allocations can happen while the thread locals are
+        // being torn down.
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        let _ = ALLOCATED_BYTES.try_with(|allocated_bytes| allocated_bytes.set(allocated_bytes.get() + size as u64));
+        record_live_change(size as i64);
+    }
+
+    fn record_live_change(change: i64) {
+        let _ = LIVE_BYTES.try_with(|live_bytes| {
+            let live = live_bytes.get() + change;
+            live_bytes.set(live);
+
+            let _ = PEAK_LIVE_BYTES.try_with(|peak_live_bytes| {
+                if live > peak_live_bytes.get() {
+                    peak_live_bytes.set(live);
+                }
+            });
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record_live_change(-(layout.size() as i64));
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record_live_change(-(layout.size() as i64));
+            record_allocation(new_size);
+            System.realloc(ptr, layout, new_size)
+        }
+
+    }
+
+}
+
+/// Runs `f` and returns the allocations it made on the current thread. Without
+/// the `mem-stats` feature the counters never move and the stats are all zero.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+    let start_allocations = ALLOCATIONS.get();
+    let start_allocated_bytes = ALLOCATED_BYTES.get();
+    let start_live_bytes = LIVE_BYTES.get();
+    PEAK_LIVE_BYTES.set(start_live_bytes);
+
+    let result = f();
+
+    let stats = MemStats {
+        allocations: ALLOCATIONS.get() - start_allocations,
+        allocated_bytes: ALLOCATED_BYTES.get() - start_allocated_bytes,
+        peak_bytes: (PEAK_LIVE_BYTES.get() - start_live_bytes).max(0) as u64
+    };
+
+    (result, stats)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use crate::scaffold::get_profile_dir;
+
+#[derive(Clone, Default)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>
+}
+
+impl Answers {
+
+    pub fn get(&self, part: usize) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => panic!("Invalid part {}", part)
+        }
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => panic!("Invalid part {}", part)
+        }
+    }
+
+}
+
+// Answers are stored one per line as `part_N = <answer>`. Multi-line answers
+// (e.g. the CRT output of year_2022::day_10) are escaped onto a single line.
+pub(crate) fn escape_answer(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+}
+
+pub(crate) fn unescape_answer(answer: &str) -> String {
+    let mut result = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(c) => result.push(c),
+            None => result.push('\\')
+        }
+    }
+
+    result
+}
+
+pub fn get_answers_path(aoc_year: &str, aoc_day: &str, profile: Option<&str>, example_input: bool) -> PathBuf {
+    let mut path = get_profile_dir(aoc_year, aoc_day, profile);
+    if example_input {
+        path.push("example_answers.txt");
+
+    } else {
+        path.push("answers.txt");
+    }
+
+    path
+}
+
+pub fn get_answers(aoc_year: &str, aoc_day: &str, profile: Option<&str>, example_input: bool) -> Answers {
+    let path = get_answers_path(aoc_year, aoc_day, profile, example_input);
+    let mut answers = Answers::default();
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return answers;
+    };
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let part = match key.trim() {
+            "part_1" => 1,
+            "part_2" => 2,
+            _ => continue
+        };
+
+        answers.set(part, unescape_answer(value.strip_prefix(' ').unwrap_or(value)));
+    }
+
+    answers
+}
+
+pub fn write_answers(aoc_year: &str, aoc_day: &str, profile: Option<&str>, example_input: bool, answers: &Answers) -> std::io::Result<()> {
+    let path = get_answers_path(aoc_year, aoc_day, profile, example_input);
+    let mut contents = String::new();
+    for part in [1, 2] {
+        if let Some(answer) = answers.get(part) {
+            contents += &format!("part_{} = {}\n", part, escape_answer(answer));
+        }
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, contents)
+}
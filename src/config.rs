@@ -0,0 +1,331 @@
+use std::{collections::HashMap, path::{Path, PathBuf}, sync::OnceLock};
+
+// Where the session token and the inputs come from, in order of precedence:
+//
+//   session token: `AOC_SESSION`, then `session` from the config file, then
+//                  `session-token.txt` in the input root
+//   input root:    `AOC_INPUT_DIR`, then `input_dir` from the config file,
+//                  then `input/` in the repo
+//
+// except that a profile picked with `--session-profile` beats the environment
+// variables for the keys its section (or the top level) sets, so a teammate
+// can't end up downloading or submitting with someone else's `AOC_SESSION`.
+//
+// The config file is `AOC_CONFIG` if set, else `$XDG_CONFIG_HOME/aoc/config`
+// (`~/.config/aoc/config`). It holds `key = value` lines, `#` comments and
+// `[name]` sections, one per session profile:
+//
+//   session_profile = alice
+//
+//   [alice]
+//   session = 53616c74...
+//   input_dir = /home/alice/aoc-input
+//
+//   [bob]
+//   session = 53616c74...
+//   input_dir = /home/bob/aoc-input
+//
+// Keys in the selected profile's section override the top level ones. The
+// profile is picked with `--session-profile`, then `AOC_SESSION_PROFILE`,
+// then `session_profile` from the top of the config file.
+
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+pub struct Config {
+    session: Option<String>,
+    input_root: PathBuf
+}
+
+impl Config {
+
+    pub fn input_root(&self) -> PathBuf {
+        self.input_root.clone()
+    }
+
+    pub fn session_token_file(&self) -> PathBuf {
+        self.input_root.join("session-token.txt")
+    }
+
+    /// The session token, or `None` if none of the sources has one.
+    pub fn session_token(&self) -> std::io::Result<Option<String>> {
+        if let Some(session) = &self.session {
+            return Ok(Some(session.clone()));
+        }
+
+        let session_token_file = self.session_token_file();
+        if !session_token_file.is_file() {
+            return Ok(None);
+        }
+
+        Ok(Some(std::fs::read_to_string(session_token_file)?.trim().to_string()))
+    }
+
+}
+
+/// The environment variables that take part in resolving the config.
+#[derive(Default)]
+struct ConfigEnv {
+    session: Option<String>,
+    input_dir: Option<PathBuf>,
+    session_profile: Option<String>
+}
+
+impl ConfigEnv {
+
+    fn read() -> Self {
+        ConfigEnv {
+            session: std::env::var("AOC_SESSION").ok().map(|session| session.trim().to_string()),
+            input_dir: std::env::var_os("AOC_INPUT_DIR").map(PathBuf::from),
+            session_profile: std::env::var("AOC_SESSION_PROFILE").ok()
+        }
+    }
+
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(config_dir) => PathBuf::from(config_dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config")
+    };
+
+    Some(config_dir.join("aoc").join("config"))
+}
+
+/// Splits a config file into its sections, the keys before the first section
+/// going into the `""` one.
+fn parse_config(contents: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections = HashMap::<String, HashMap<String, String>>::new();
+    let mut section = String::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            section = name.trim().to_string();
+            sections.entry(section.clone()).or_default();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            sections.entry(section.clone()).or_default().insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
+
+/// Applies the precedence rules to the sections of the config file, which
+/// lives in `config_dir`. Returns the config along with warnings about
+/// environment variables overridden by `selected_profile`.
+fn resolve_config(sections: &HashMap<String, HashMap<String, String>>, config_dir: &Path, selected_profile: Option<&str>, env: ConfigEnv) -> Result<(Config, Vec<String>), String> {
+    let top_level = sections.get("").cloned().unwrap_or_default();
+    let profile =
+        selected_profile.map(str::to_string)
+            .or(env.session_profile)
+            .or_else(|| top_level.get("session_profile").cloned());
+
+    let mut values = top_level;
+    if let Some(profile) = &profile {
+        let Some(section) = sections.get(profile) else {
+            return Err(format!("unknown session profile `{}`, add a [{}] section to the config file", profile, profile));
+        };
+
+        values.extend(section.clone());
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(profile) = selected_profile {
+        for (variable, key, is_set) in [("AOC_SESSION", "session", env.session.is_some()), ("AOC_INPUT_DIR", "input_dir", env.input_dir.is_some())] {
+            if is_set && values.contains_key(key) {
+                warnings.push(format!("Ignoring {} in favor of `{}` from session profile {}", variable, key, profile));
+            }
+        }
+    }
+
+    // Relative directories are relative to the config file, not to wherever
+    // the binary happens to be launched from.
+    let session = values.get("session").cloned();
+    let input_dir = values.get("input_dir").map(|input_dir| config_dir.join(input_dir));
+    let config = if selected_profile.is_some() {
+        Config {
+            session: session.or(env.session),
+            input_root: input_dir.or(env.input_dir).unwrap_or_else(default_input_root)
+        }
+
+    } else {
+        Config {
+            session: env.session.or(session),
+            input_root: env.input_dir.or(input_dir).unwrap_or_else(default_input_root)
+        }
+    };
+
+    Ok((config, warnings))
+}
+
+fn default_input_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+fn load_config() -> Result<Config, String> {
+    let path = config_path().filter(|path| path.is_file());
+    let sections = match &path {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(contents) => parse_config(&contents),
+            Err(error) => return Err(format!("failed to read config file at {:?}: {}", path, error))
+        },
+        None => HashMap::new()
+    };
+
+    let config_dir = path.as_deref().and_then(Path::parent).unwrap_or(Path::new(""));
+    let (config, warnings) =
+        resolve_config(&sections, config_dir, SELECTED_PROFILE.get().map(String::as_str), ConfigEnv::read())
+            .map_err(|error| format!("{} at {:?}", error, path.unwrap_or_default()))?;
+
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+
+    Ok(config)
+}
+
+/// Selects the session profile to use, which has to happen before the config
+/// is first used.
+pub fn select_session_profile(profile: String) {
+    if SELECTED_PROFILE.set(profile).is_err() || CONFIG.get().is_some() {
+        panic!("Session profile selected after the config was loaded");
+    }
+}
+
+/// Loads the config, reporting a config file that can't be read or an unknown
+/// session profile. Without this the first use of `config` panics on them.
+pub fn init_config() -> Result<(), String> {
+    if CONFIG.get().is_none() {
+        let _ = CONFIG.set(load_config()?);
+    }
+
+    Ok(())
+}
+
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| load_config().unwrap_or_else(|error| panic!("Invalid config: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const CONFIG: &str = "
+        # Shared settings
+        session_profile = alice
+        session = top-level-token
+
+        [alice]
+        session = alice-token
+        input_dir = alice-input
+
+        [bob]
+        input_dir = /home/bob/aoc-input
+
+        [carol]
+    ";
+
+    fn env(session: Option<&str>, input_dir: Option<&str>, session_profile: Option<&str>) -> ConfigEnv {
+        ConfigEnv {
+            session: session.map(str::to_string),
+            input_dir: input_dir.map(PathBuf::from),
+            session_profile: session_profile.map(str::to_string)
+        }
+    }
+
+    fn resolve(contents: &str, selected_profile: Option<&str>, env: ConfigEnv) -> (Option<String>, PathBuf, Vec<String>) {
+        let (config, warnings) = resolve_config(&parse_config(contents), Path::new("/config"), selected_profile, env).unwrap();
+        (config.session, config.input_root, warnings)
+    }
+
+    #[test]
+    fn parses_sections() {
+        let sections = parse_config(CONFIG);
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[""]["session_profile"], "alice");
+        assert_eq!(sections[""]["session"], "top-level-token");
+        assert_eq!(sections["alice"]["input_dir"], "alice-input");
+        assert_eq!(sections["bob"].get("session"), None);
+        assert!(sections["carol"].is_empty());
+        assert!(parse_config("# nothing\n\n").is_empty());
+    }
+
+    #[test]
+    fn defaults_without_config_or_env() {
+        let (session, input_root, warnings) = resolve("", None, ConfigEnv::default());
+        assert_eq!(session, None);
+        assert_eq!(input_root, default_input_root());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn env_beats_config_file() {
+        let (session, input_root, warnings) = resolve(CONFIG, None, env(Some("env-token"), Some("/env-input"), None));
+        assert_eq!(session.as_deref(), Some("env-token"));
+        assert_eq!(input_root, PathBuf::from("/env-input"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn config_file_beats_defaults() {
+        let (session, input_root, _) = resolve(CONFIG, None, ConfigEnv::default());
+        assert_eq!(session.as_deref(), Some("alice-token"));
+        assert_eq!(input_root, PathBuf::from("/config/alice-input"));
+
+        let (session, input_root, _) = resolve("session = token\ninput_dir = /input", None, ConfigEnv::default());
+        assert_eq!(session.as_deref(), Some("token"));
+        assert_eq!(input_root, PathBuf::from("/input"));
+    }
+
+    #[test]
+    fn profile_section_overrides_top_level() {
+        let (session, input_root, _) = resolve(CONFIG, None, env(None, None, Some("bob")));
+        assert_eq!(session.as_deref(), Some("top-level-token"));
+        assert_eq!(input_root, PathBuf::from("/home/bob/aoc-input"));
+    }
+
+    #[test]
+    fn selected_profile_beats_env_profile_and_config_profile() {
+        let (_, input_root, _) = resolve(CONFIG, Some("bob"), env(None, None, Some("alice")));
+        assert_eq!(input_root, PathBuf::from("/home/bob/aoc-input"));
+
+        let (_, input_root, _) = resolve(CONFIG, None, env(None, None, Some("carol")));
+        assert_eq!(input_root, default_input_root());
+    }
+
+    #[test]
+    fn selected_profile_beats_env_for_the_keys_it_sets() {
+        let (session, input_root, warnings) = resolve(CONFIG, Some("alice"), env(Some("env-token"), Some("/env-input"), None));
+        assert_eq!(session.as_deref(), Some("alice-token"));
+        assert_eq!(input_root, PathBuf::from("/config/alice-input"));
+        assert_eq!(warnings, [
+            "Ignoring AOC_SESSION in favor of `session` from session profile alice",
+            "Ignoring AOC_INPUT_DIR in favor of `input_dir` from session profile alice"
+        ]);
+
+        let (session, input_root, warnings) = resolve("[dave]\nsession = dave-token", Some("dave"), env(Some("env-token"), Some("/env-input"), None));
+        assert_eq!(session.as_deref(), Some("dave-token"));
+        assert_eq!(input_root, PathBuf::from("/env-input"));
+        assert_eq!(warnings, ["Ignoring AOC_SESSION in favor of `session` from session profile dave"]);
+    }
+
+    #[test]
+    fn rejects_unknown_profiles() {
+        let error = resolve_config(&parse_config(CONFIG), Path::new("/config"), Some("eve"), ConfigEnv::default()).err().unwrap();
+        assert!(error.contains("unknown session profile `eve`"));
+        assert!(resolve_config(&HashMap::new(), Path::new(""), None, env(None, None, Some("eve"))).is_err());
+    }
+
+}
@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+use crate::scaffold::AocResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputPosition {
+    pub line: usize,
+    pub column: usize
+}
+
+/// An error returned by a solver, typically because the input isn't what it
+/// expected. The year, day and part are filled in by the `aoc_solvers!`
+/// wrappers, so solvers only need to provide the message and, when they know
+/// it, where in the input things went wrong.
+#[derive(Clone, Debug)]
+pub struct AocError {
+    pub year: &'static str,
+    pub day: &'static str,
+    /// `None` when the error happened while parsing the input of a `Solver`.
+    pub part: Option<usize>,
+    pub message: String,
+    pub position: Option<InputPosition>
+}
+
+impl AocError {
+
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            year: "",
+            day: "",
+            part: None,
+            message: message.into(),
+            position: None
+        }
+    }
+
+    /// Sets the 1-based line and column of the input the error is about.
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.position = Some(InputPosition { line, column });
+        self
+    }
+
+    /// The message and position, without the year, day and part.
+    pub fn details(&self) -> String {
+        match self.position {
+            Some(position) => format!("{} (input line {}, column {})", self.message, position.line, position.column),
+            None => self.message.clone()
+        }
+    }
+
+    pub fn with_context(mut self, year: &'static str, day: &'static str, part: Option<usize>) -> Self {
+        self.year = year;
+        self.day = day;
+        self.part = part;
+        self
+    }
+
+}
+
+impl Display for AocError {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.year.is_empty() {
+            match self.part {
+                Some(part) => write!(f, "{} {} part {}: ", self.year, self.day, part)?,
+                None => write!(f, "{} {} parse: ", self.year, self.day)?
+            }
+        }
+
+        write!(f, "{}", self.details())
+    }
+
+}
+
+impl std::error::Error for AocError {}
+
+impl From<String> for AocError {
+
+    fn from(message: String) -> Self {
+        AocError::new(message)
+    }
+
+}
+
+impl From<&str> for AocError {
+
+    fn from(message: &str) -> Self {
+        AocError::new(message)
+    }
+
+}
+
+impl From<std::num::ParseIntError> for AocError {
+
+    fn from(error: std::num::ParseIntError) -> Self {
+        AocError::new(format!("invalid number: {}", error))
+    }
+
+}
+
+/// What a solver may return: anything convertible to an `AocResult`, or a
+/// `Result` of one.
+pub trait IntoAocResult {
+    fn into_aoc_result(self) -> Result<AocResult, AocError>;
+}
+
+impl<T: Into<AocResult>> IntoAocResult for T {
+
+    fn into_aoc_result(self) -> Result<AocResult, AocError> {
+        Ok(self.into())
+    }
+
+}
+
+impl<T: Into<AocResult>> IntoAocResult for Result<T, AocError> {
+
+    fn into_aoc_result(self) -> Result<AocResult, AocError> {
+        self.map(Into::into)
+    }
+
+}
@@ -0,0 +1,316 @@
+use std::{fs::OpenOptions, io::Write, path::{Path, PathBuf}, process::Command, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use crate::runner::DayResult;
+
+// Each line of a history file is one part of one run:
+// `timestamp,revision,year,day,part,duration_ns`. Runs are told apart by their
+// timestamp, which is the unix time in milliseconds the run was recorded at.
+const HISTORY_FILE: &str = "history.csv";
+const BASELINE_DIR: &str = "baselines";
+
+#[derive(Clone)]
+pub struct HistoryRecord {
+    pub year: String,
+    pub day: String,
+    pub part: usize,
+    pub duration: Duration
+}
+
+#[derive(Clone)]
+pub struct HistoryRun {
+    pub timestamp: u64,
+    pub revision: String,
+    pub records: Vec<HistoryRecord>
+}
+
+impl HistoryRun {
+
+    pub fn new() -> Self {
+        let timestamp =
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_millis() as u64);
+
+        Self {
+            timestamp,
+            revision: git_revision(),
+            records: Vec::new()
+        }
+    }
+
+    /// Adds the parts of `result` that produced an answer with their default
+    /// implementation.
+    pub fn add_day(&mut self, result: &DayResult) {
+        for (part, part_result) in result.parts() {
+            if part_result.outcome.answer().is_some() && part_result.variant.is_none() {
+                self.records.push(HistoryRecord {
+                    year: result.year.to_string(),
+                    day: result.day.to_string(),
+                    part,
+                    duration: part_result.duration
+                });
+            }
+        }
+    }
+
+    pub fn find(&self, year: &str, day: &str, part: usize) -> Option<&HistoryRecord> {
+        self.records
+            .iter()
+            .find(|record| record.year == year && record.day == day && record.part == part)
+    }
+
+    fn to_lines(&self) -> String {
+        let mut lines = String::new();
+        for record in &self.records {
+            lines += &format!(
+                "{},{},{},{},{},{}\n",
+                self.timestamp,
+                self.revision,
+                record.year,
+                record.day,
+                record.part,
+                record.duration.as_nanos());
+        }
+
+        lines
+    }
+
+}
+
+fn git_revision() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().replace(',', "_"))
+        .filter(|revision| !revision.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Formats a unix timestamp in milliseconds as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let secs = timestamp / 1000;
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let secs_of_day = secs % 86400;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60,
+        secs_of_day % 60)
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date, using
+/// Howard Hinnant's `civil_from_days` algorithm.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn parse_runs(contents: &str) -> Vec<HistoryRun> {
+    let mut runs: Vec<HistoryRun> = Vec::new();
+    for line in contents.lines() {
+        let fields = line.split(',').collect::<Vec<_>>();
+        let &[timestamp, revision, year, day, part, duration_ns] = fields.as_slice() else {
+            continue;
+        };
+
+        let (Ok(timestamp), Ok(part), Ok(duration_ns)) = (timestamp.parse::<u64>(), part.parse::<usize>(), duration_ns.parse::<u64>()) else {
+            continue;
+        };
+
+        let record = HistoryRecord {
+            year: year.to_string(),
+            day: day.to_string(),
+            part,
+            duration: Duration::from_nanos(duration_ns)
+        };
+
+        match runs.last_mut() {
+            Some(run) if run.timestamp == timestamp => run.records.push(record),
+            _ => runs.push(HistoryRun {
+                timestamp,
+                revision: revision.to_string(),
+                records: vec![record]
+            })
+        }
+    }
+
+    runs
+}
+
+/// Checks a baseline name, which becomes a file name in the baselines
+/// directory and so can't point outside of it.
+pub fn parse_baseline_name(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("invalid baseline name `{}`, it can't be empty or contain `/`, `\\` or `..`", name));
+    }
+
+    Ok(name.to_string())
+}
+
+fn get_baseline_path(history_dir: &Path, name: &str) -> PathBuf {
+    history_dir.join(BASELINE_DIR).join(format!("{}.csv", name))
+}
+
+pub fn append_run(history_dir: &Path, run: &HistoryRun) -> std::io::Result<()> {
+    std::fs::create_dir_all(history_dir)?;
+
+    let mut file =
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(history_dir.join(HISTORY_FILE))?;
+
+    file.write_all(run.to_lines().as_bytes())
+}
+
+pub fn load_runs(history_dir: &Path) -> Vec<HistoryRun> {
+    std::fs::read_to_string(history_dir.join(HISTORY_FILE))
+        .map(|contents| parse_runs(&contents))
+        .unwrap_or_default()
+}
+
+pub fn save_baseline(history_dir: &Path, name: &str, run: &HistoryRun) -> std::io::Result<()> {
+    let path = get_baseline_path(history_dir, name);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, run.to_lines())
+}
+
+pub fn load_baseline(history_dir: &Path, name: &str) -> Option<HistoryRun> {
+    let contents = std::fs::read_to_string(get_baseline_path(history_dir, name)).ok()?;
+    parse_runs(&contents).pop()
+}
+
+/// Prints the change of every part in `current` against `previous` and returns
+/// the number of parts that got slower by more than `threshold` percent.
+pub fn print_comparison(previous: &HistoryRun, current: &HistoryRun, threshold: f64) -> usize {
+    println!(
+        "Comparing {} ({}) against {} ({}):",
+        format_timestamp(current.timestamp),
+        current.revision,
+        format_timestamp(previous.timestamp),
+        previous.revision);
+
+    let mut regressions = 0;
+    let mut current_year = None;
+    for record in &current.records {
+        let Some(previous_record) = previous.find(&record.year, &record.day, record.part) else {
+            continue;
+        };
+
+        if current_year != Some(&record.year) {
+            current_year = Some(&record.year);
+            println!("{}:", record.year);
+        }
+
+        let previous_secs = previous_record.duration.as_secs_f64();
+        let change = if previous_secs > 0.0 {
+            (record.duration.as_secs_f64() - previous_secs) / previous_secs * 100.0
+
+        } else {
+            0.0
+        };
+
+        let marker = if change > threshold {
+            regressions += 1;
+            "  SLOWER"
+
+        } else if change < -threshold {
+            "  faster"
+
+        } else {
+            ""
+        };
+
+        println!(
+            "  - {} part {}: {:?} -> {:?} ({:+.1}%){}",
+            record.day,
+            record.part,
+            previous_record.duration,
+            record.duration,
+            change,
+            marker);
+    }
+
+    println!("{} part(s) got more than {}% slower", regressions, threshold);
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const HISTORY: &str = concat!(
+        "1700000000000,abc123,year_2022,day_01,1,1500\n",
+        "1700000000000,abc123,year_2022,day_01,2,2500\n",
+        "not,a,valid,line\n",
+        "1700000000000,abc123,year_2022,day_02,x,100\n",
+        "1700000360000,def456-dirty,year_2022,day_01,1,1400\n");
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        assert_eq!(civil_from_days(11017), (2000, 3, 1));
+        assert_eq!(civil_from_days(19723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(civil_from_days(19783), (2024, 3, 1));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(1700000000123), "2023-11-14 22:13:20 UTC");
+    }
+
+    #[test]
+    fn parses_runs_grouped_by_timestamp() {
+        let runs = parse_runs(HISTORY);
+        assert_eq!(runs.len(), 2);
+
+        assert_eq!(runs[0].timestamp, 1700000000000);
+        assert_eq!(runs[0].revision, "abc123");
+        assert_eq!(runs[0].records.len(), 2);
+        assert_eq!(runs[0].find("year_2022", "day_01", 2).map(|record| record.duration), Some(Duration::from_nanos(2500)));
+        assert!(runs[0].find("year_2022", "day_02", 1).is_none());
+
+        assert_eq!(runs[1].revision, "def456-dirty");
+        assert_eq!(runs[1].find("year_2022", "day_01", 1).map(|record| record.duration), Some(Duration::from_nanos(1400)));
+    }
+
+    #[test]
+    fn round_trips_runs_through_lines() {
+        let runs = parse_runs(HISTORY);
+        let lines = runs.iter().map(HistoryRun::to_lines).collect::<String>();
+        let reparsed = parse_runs(&lines);
+        assert_eq!(reparsed.iter().map(HistoryRun::to_lines).collect::<String>(), lines);
+        assert_eq!(lines.lines().count(), 3);
+    }
+
+    #[test]
+    fn rejects_baseline_names_outside_the_baselines_dir() {
+        assert_eq!(parse_baseline_name("before-refactor"), Ok("before-refactor".to_string()));
+        assert_eq!(parse_baseline_name("v1.2"), Ok("v1.2".to_string()));
+        for name in ["", "../../foo", "..", "a/b", "a\\b", "/tmp/foo"] {
+            assert!(parse_baseline_name(name).is_err(), "{:?} should be rejected", name);
+        }
+    }
+
+}
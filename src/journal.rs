@@ -0,0 +1,269 @@
+use std::{fmt::Display, fs::OpenOptions, io::Write, path::{Path, PathBuf}};
+
+use clap::ValueEnum;
+
+use crate::answers::{escape_answer, unescape_answer};
+use crate::scaffold::get_input_dir;
+use crate::submit::SubmitVerdict;
+
+// The journal keeps every answer tried for a day, one per line as
+// `part_N <verdict> = <answer>`, so wrong answers aren't submitted twice.
+const JOURNAL_FILE: &str = "journal.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow
+}
+
+impl Verdict {
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low"
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            _ => None
+        }
+    }
+
+    /// The verdict to journal for a submission, if the site gave one.
+    pub fn from_submit_verdict(verdict: &SubmitVerdict) -> Option<Self> {
+        match verdict {
+            SubmitVerdict::Correct => Some(Verdict::Correct),
+            SubmitVerdict::Wrong => Some(Verdict::Wrong),
+            SubmitVerdict::TooHigh => Some(Verdict::TooHigh),
+            SubmitVerdict::TooLow => Some(Verdict::TooLow),
+            _ => None
+        }
+    }
+
+}
+
+impl Display for Verdict {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name().replace('_', " "))
+    }
+
+}
+
+pub struct JournalEntry {
+    pub part: usize,
+    pub verdict: Verdict,
+    pub answer: String
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum JournalCheck {
+    /// The answer matches the recorded correct answer.
+    Confirmed,
+    /// A correct answer is recorded and this isn't it.
+    NotCorrect(String),
+    /// The answer was already tried and was wrong.
+    KnownWrong(Verdict),
+    /// The answer is at or above an answer that was too high.
+    AboveBound(i128),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(i128),
+    /// Nothing is known about the answer.
+    Unknown
+}
+
+impl JournalCheck {
+
+    pub fn is_wrong(&self) -> bool {
+        !matches!(self, JournalCheck::Confirmed | JournalCheck::Unknown)
+    }
+
+}
+
+impl Display for JournalCheck {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalCheck::Confirmed => write!(f, "CONFIRMED (matches the journaled correct answer)"),
+            JournalCheck::NotCorrect(correct) => write!(f, "WARNING: the journaled correct answer is {}", correct),
+            JournalCheck::KnownWrong(verdict) => write!(f, "WARNING: already tried this answer and it was {}", verdict),
+            JournalCheck::AboveBound(bound) => write!(f, "WARNING: {} was already too high", bound),
+            JournalCheck::BelowBound(bound) => write!(f, "WARNING: {} was already too low", bound),
+            JournalCheck::Unknown => write!(f, "not in the journal")
+        }
+    }
+
+}
+
+fn get_journal_path(aoc_year: &str, aoc_day: &str) -> PathBuf {
+    get_input_dir(aoc_year, aoc_day).join(JOURNAL_FILE)
+}
+
+pub fn load_journal(aoc_year: &str, aoc_day: &str) -> Vec<JournalEntry> {
+    read_journal(&get_journal_path(aoc_year, aoc_day))
+}
+
+fn read_journal(path: &Path) -> Vec<JournalEntry> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, answer) = line.split_once(" = ")?;
+            let (part, verdict) = key.split_once(' ')?;
+            Some(JournalEntry {
+                part: part.strip_prefix("part_")?.parse().ok()?,
+                verdict: Verdict::from_name(verdict)?,
+                answer: unescape_answer(answer)
+            })
+        })
+        .collect()
+}
+
+pub fn append_journal(aoc_year: &str, aoc_day: &str, part: usize, verdict: Verdict, answer: &str) -> std::io::Result<()> {
+    write_journal_entry(&get_journal_path(aoc_year, aoc_day), part, verdict, answer)
+}
+
+fn write_journal_entry(path: &Path, part: usize, verdict: Verdict, answer: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "part_{} {} = {}", part, verdict.name(), escape_answer(answer))
+}
+
+pub fn check_answer(journal: &[JournalEntry], part: usize, answer: &str) -> JournalCheck {
+    let entries = journal.iter().filter(|entry| entry.part == part).collect::<Vec<_>>();
+    if let Some(correct) = entries.iter().find(|entry| entry.verdict == Verdict::Correct) {
+        return if correct.answer == answer {
+            JournalCheck::Confirmed
+
+        } else {
+            JournalCheck::NotCorrect(correct.answer.clone())
+        };
+    }
+
+    if let Some(entry) = entries.iter().find(|entry| entry.answer == answer) {
+        return JournalCheck::KnownWrong(entry.verdict);
+    }
+
+    let Ok(value) = answer.trim().parse::<i128>() else {
+        return JournalCheck::Unknown;
+    };
+
+    let bound = |verdict| {
+        entries
+            .iter()
+            .filter(move |entry| entry.verdict == verdict)
+            .filter_map(|entry| entry.answer.trim().parse::<i128>().ok())
+    };
+
+    if let Some(upper_bound) = bound(Verdict::TooHigh).min() {
+        if value >= upper_bound {
+            return JournalCheck::AboveBound(upper_bound);
+        }
+    }
+
+    if let Some(lower_bound) = bound(Verdict::TooLow).max() {
+        if value <= lower_bound {
+            return JournalCheck::BelowBound(lower_bound);
+        }
+    }
+
+    JournalCheck::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn entry(part: usize, verdict: Verdict, answer: &str) -> JournalEntry {
+        JournalEntry { part, verdict, answer: answer.to_string() }
+    }
+
+    fn bounded_journal() -> Vec<JournalEntry> {
+        vec![
+            entry(1, Verdict::TooHigh, "500"),
+            entry(1, Verdict::TooHigh, "400"),
+            entry(1, Verdict::TooLow, "100"),
+            entry(1, Verdict::TooLow, "200"),
+            entry(1, Verdict::Wrong, "300"),
+            entry(2, Verdict::Correct, "42")
+        ]
+    }
+
+    #[test]
+    fn checks_answers_against_bounds() {
+        let journal = bounded_journal();
+        assert_eq!(check_answer(&journal, 1, "450"), JournalCheck::AboveBound(400));
+        assert_eq!(check_answer(&journal, 1, "401"), JournalCheck::AboveBound(400));
+        assert_eq!(check_answer(&journal, 1, "150"), JournalCheck::BelowBound(200));
+        assert_eq!(check_answer(&journal, 1, "-7"), JournalCheck::BelowBound(200));
+        assert_eq!(check_answer(&journal, 1, "250"), JournalCheck::Unknown);
+        assert_eq!(check_answer(&journal, 1, "399"), JournalCheck::Unknown);
+        assert_eq!(check_answer(&journal, 1, "201"), JournalCheck::Unknown);
+    }
+
+    #[test]
+    fn answers_equal_to_a_bound_are_known_wrong() {
+        let journal = bounded_journal();
+        assert_eq!(check_answer(&journal, 1, "400"), JournalCheck::KnownWrong(Verdict::TooHigh));
+        assert_eq!(check_answer(&journal, 1, "200"), JournalCheck::KnownWrong(Verdict::TooLow));
+        assert_eq!(check_answer(&journal, 1, "300"), JournalCheck::KnownWrong(Verdict::Wrong));
+
+        let journal = vec![entry(1, Verdict::TooHigh, "400"), entry(1, Verdict::TooLow, "200")];
+        assert_eq!(check_answer(&journal, 1, " 400"), JournalCheck::AboveBound(400));
+        assert_eq!(check_answer(&journal, 1, "200 "), JournalCheck::BelowBound(200));
+    }
+
+    #[test]
+    fn non_numeric_answers_only_match_exactly() {
+        let journal = vec![entry(1, Verdict::TooHigh, "100"), entry(1, Verdict::Wrong, "ABC")];
+        assert_eq!(check_answer(&journal, 1, "ABC"), JournalCheck::KnownWrong(Verdict::Wrong));
+        assert_eq!(check_answer(&journal, 1, "XYZ"), JournalCheck::Unknown);
+        assert_eq!(check_answer(&journal, 1, "1,2"), JournalCheck::Unknown);
+    }
+
+    #[test]
+    fn correct_answer_overrides_everything_else() {
+        let mut journal = bounded_journal();
+        journal.push(entry(1, Verdict::Correct, "300"));
+        assert_eq!(check_answer(&journal, 1, "300"), JournalCheck::Confirmed);
+        assert_eq!(check_answer(&journal, 1, "450"), JournalCheck::NotCorrect("300".to_string()));
+        assert_eq!(check_answer(&journal, 1, "250"), JournalCheck::NotCorrect("300".to_string()));
+
+        assert_eq!(check_answer(&journal, 2, "42"), JournalCheck::Confirmed);
+        assert_eq!(check_answer(&journal, 2, "500"), JournalCheck::NotCorrect("42".to_string()));
+        assert_eq!(check_answer(&[], 1, "1"), JournalCheck::Unknown);
+    }
+
+    #[test]
+    fn round_trips_entries_through_the_journal_file() {
+        let dir = std::env::temp_dir().join(format!("aoc-journal-test-{}", std::process::id()));
+        let path = dir.join(JOURNAL_FILE);
+        assert!(read_journal(&path).is_empty());
+
+        write_journal_entry(&path, 1, Verdict::TooLow, "12").unwrap();
+        write_journal_entry(&path, 1, Verdict::Wrong, "a = b\nc").unwrap();
+        write_journal_entry(&path, 2, Verdict::Correct, "XYZ").unwrap();
+
+        let journal = read_journal(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let entries = journal.iter().map(|entry| (entry.part, entry.verdict, entry.answer.as_str())).collect::<Vec<_>>();
+        assert_eq!(entries, [(1, Verdict::TooLow, "12"), (1, Verdict::Wrong, "a = b\nc"), (2, Verdict::Correct, "XYZ")]);
+    }
+
+}
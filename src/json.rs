@@ -0,0 +1,252 @@
+// A small JSON reader, just enough for the responses of the site. Writing JSON
+// is done by hand where it's needed, see `report.rs`.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>)
+}
+
+impl JsonValue {
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, JsonValue)] {
+        match self {
+            JsonValue::Object(entries) => entries,
+            _ => &[]
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonValue::Number(value) if *value >= 0.0 && value.fract() == 0.0 => Some(*value as u64),
+            _ => None
+        }
+    }
+
+}
+
+struct JsonParser<'a> {
+    input: &'a [u8],
+    position: usize
+}
+
+impl<'a> JsonParser<'a> {
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at byte {}", message, self.position)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.position < self.input.len() && self.input[self.position].is_ascii_whitespace() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.position).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected '{}'", expected as char)));
+        }
+
+        self.position += 1;
+        Ok(())
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, String> {
+        if !self.input[self.position..].starts_with(literal.as_bytes()) {
+            return Err(self.error("invalid literal"));
+        }
+
+        self.position += literal.len();
+        Ok(value)
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&c) = self.input.get(self.position) else {
+                return Err(self.error("unterminated string"));
+            };
+
+            self.position += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escaped) = self.input.get(self.position) else {
+                        return Err(self.error("unterminated string"));
+                    };
+
+                    self.position += 1;
+                    match escaped {
+                        b'n' => bytes.push(b'\n'),
+                        b't' => bytes.push(b'\t'),
+                        b'r' => bytes.push(b'\r'),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0c),
+                        b'u' => {
+                            let hex = self.input.get(self.position..self.position + 4).ok_or_else(|| self.error("invalid escape"))?;
+                            let code = u32::from_str_radix(std::str::from_utf8(hex).unwrap_or(""), 16).map_err(|_| self.error("invalid escape"))?;
+                            self.position += 4;
+
+                            let c = char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER);
+                            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        },
+                        escaped => bytes.push(escaped)
+                    }
+                },
+                c => bytes.push(c)
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.position;
+        while self.position < self.input.len() && matches!(self.input[self.position], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+            self.position += 1;
+        }
+
+        std::str::from_utf8(&self.input[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .map(JsonValue::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b'[') => {
+                self.position += 1;
+                let mut values = Vec::new();
+                if self.peek() == Some(b']') {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(values));
+                }
+
+                loop {
+                    values.push(self.parse_value()?);
+                    if self.peek() == Some(b',') {
+                        self.position += 1;
+                        continue;
+                    }
+
+                    self.expect(b']')?;
+                    return Ok(JsonValue::Array(values));
+                }
+            },
+            Some(b'{') => {
+                self.position += 1;
+                let mut entries = Vec::new();
+                if self.peek() == Some(b'}') {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(entries));
+                }
+
+                loop {
+                    self.skip_whitespace();
+                    let key = self.parse_string()?;
+                    self.expect(b':')?;
+                    entries.push((key, self.parse_value()?));
+                    if self.peek() == Some(b',') {
+                        self.position += 1;
+                        continue;
+                    }
+
+                    self.expect(b'}')?;
+                    return Ok(JsonValue::Object(entries));
+                }
+            },
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.error("unexpected character"))
+        }
+    }
+
+}
+
+pub fn parse_json(input: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser {
+        input: input.as_bytes(),
+        position: 0
+    };
+
+    let value = parser.parse_value()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("trailing characters"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parses_literals_and_numbers() {
+        assert_eq!(parse_json("null"), Ok(JsonValue::Null));
+        assert_eq!(parse_json(" true "), Ok(JsonValue::Bool(true)));
+        assert_eq!(parse_json("false"), Ok(JsonValue::Bool(false)));
+        assert_eq!(parse_json("-12.5e1"), Ok(JsonValue::Number(-125.0)));
+        assert_eq!(parse_json("1669871000").unwrap().as_u64(), Some(1669871000));
+        assert_eq!(parse_json("-1").unwrap().as_u64(), None);
+        assert_eq!(parse_json("1.5").unwrap().as_u64(), None);
+    }
+
+    #[test]
+    fn parses_string_escapes() {
+        let value = parse_json(r#""a\"b\\c\/d\n\t\u00e9\u2603""#).unwrap();
+        assert_eq!(value.as_str(), Some("a\"b\\c/d\n\té☃"));
+        assert_eq!(parse_json(r#""naïve""#).unwrap().as_str(), Some("naïve"));
+    }
+
+    #[test]
+    fn parses_nested_arrays_and_objects() {
+        let value = parse_json(r#"{"a": [1, [], {}], "b": {"c": "d"}, "e": {}}"#).unwrap();
+        assert_eq!(
+            value.get("a"),
+            Some(&JsonValue::Array(vec![JsonValue::Number(1.0), JsonValue::Array(vec![]), JsonValue::Object(vec![])])));
+
+        assert_eq!(value.get("b").and_then(|b| b.get("c")).and_then(JsonValue::as_str), Some("d"));
+        assert_eq!(value.entries().iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>(), ["a", "b", "e"]);
+        assert_eq!(value.get("missing"), None);
+        assert!(JsonValue::Null.entries().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        for input in ["", "nul", "[1,", "[1 2]", "{\"a\" 1}", "{a: 1}", "\"abc", "\"\\u12\"", "1 2", "{} x"] {
+            assert!(parse_json(input).is_err(), "{:?} should not parse", input);
+        }
+    }
+
+}
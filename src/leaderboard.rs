@@ -0,0 +1,221 @@
+use std::{path::PathBuf, time::{Duration, UNIX_EPOCH}};
+
+use crate::json::{JsonValue, parse_json};
+use crate::scaffold::{DownloadError, build_client, check_status, get_base_url, get_input_root};
+use crate::unlock::{format_countdown, unlock_time};
+
+// The site asks not to fetch a private leaderboard more than once every 15
+// minutes, so the JSON is cached in `input/leaderboards/`.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+pub struct Member {
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Unix timestamps of the stars of each day, indexed by `day - 1`.
+    pub star_times: [[Option<u64>; 2]; 25]
+}
+
+pub struct Leaderboard {
+    pub year: i64,
+    pub members: Vec<Member>
+}
+
+fn parse_member(id: &str, member: &JsonValue) -> Option<Member> {
+    let name = match member.get("name").and_then(JsonValue::as_str) {
+        Some(name) => name.to_string(),
+        None => format!("(anonymous user #{})", id)
+    };
+
+    let mut star_times = [[None; 2]; 25];
+    for (day, parts) in member.get("completion_day_level")?.entries() {
+        let Some(day_times) = day.parse::<usize>().ok().and_then(|day| star_times.get_mut(day.wrapping_sub(1))) else {
+            continue;
+        };
+
+        for (part, star) in parts.entries() {
+            let Some(index) = part.parse::<usize>().ok().filter(|part| (1..=2).contains(part)) else {
+                continue;
+            };
+
+            day_times[index - 1] = star.get("get_star_ts").and_then(JsonValue::as_u64);
+        }
+    }
+
+    Some(Member {
+        name,
+        local_score: member.get("local_score")?.as_u64()?,
+        stars: member.get("stars")?.as_u64()?,
+        star_times
+    })
+}
+
+/// Parses the JSON of a private leaderboard, with the members sorted by their
+/// local score.
+pub fn parse_leaderboard(json: &str) -> Result<Leaderboard, String> {
+    let json = parse_json(json)?;
+    let year =
+        json.get("event")
+            .and_then(JsonValue::as_str)
+            .and_then(|event| event.parse().ok())
+            .ok_or("missing event year")?;
+
+    let mut members =
+        json.get("members")
+            .ok_or("missing members")?
+            .entries()
+            .iter()
+            .map(|(id, member)| parse_member(id, member).ok_or_else(|| format!("invalid member {}", id)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+    members.sort_by(|a, b| b.local_score.cmp(&a.local_score).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(Leaderboard {
+        year,
+        members
+    })
+}
+
+fn get_cache_path(year: u32, id: u64) -> PathBuf {
+    get_input_root().join("leaderboards").join(format!("{}_{}.json", year, id))
+}
+
+fn download_leaderboard(year: u32, id: u64) -> Result<String, DownloadError> {
+    let url =
+        get_base_url()
+            .and_then(|base_url| Ok(base_url.join(&format!("{}/leaderboard/private/view/{}.json", year, id))?))
+            .map_err(|error| DownloadError::Request(error.to_string()))?;
+
+    let response = build_client()?.get(url).send()?;
+    check_status(response.status().as_u16())?;
+
+    // Without a valid session the site redirects to the login page.
+    let json = response.text()?;
+    if !json.trim_start().starts_with('{') {
+        return Err(DownloadError::BadSessionToken);
+    }
+
+    Ok(json)
+}
+
+/// The JSON of a leaderboard, from the cache unless it's older than the
+/// minimum refresh interval.
+pub fn get_leaderboard_json(year: u32, id: u64) -> Result<String, DownloadError> {
+    let cache_path = get_cache_path(year, id);
+    let cache_age =
+        std::fs::metadata(&cache_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+
+    if let Some(age) = cache_age.filter(|age| *age < MIN_REFRESH_INTERVAL) {
+        eprintln!("Using the cached leaderboard, it can be refreshed in {}", format_countdown(MIN_REFRESH_INTERVAL - age));
+        return Ok(std::fs::read_to_string(cache_path)?);
+    }
+
+    let json = match download_leaderboard(year, id) {
+        Ok(json) => json,
+        Err(error) if cache_path.is_file() => {
+            eprintln!("Failed to refresh the leaderboard, using the cached one: {}", error);
+            return Ok(std::fs::read_to_string(cache_path)?);
+        },
+        Err(error) => return Err(error)
+    };
+
+    std::fs::create_dir_all(cache_path.parent().unwrap())?;
+    std::fs::write(&cache_path, &json)?;
+
+    Ok(json)
+}
+
+fn day_stars(member: &Member, day: usize) -> char {
+    match member.star_times[day] {
+        [Some(_), Some(_)] => '*',
+        [Some(_), None] => '.',
+        _ => ' '
+    }
+}
+
+/// Prints the members with their stars per day (`*` for both parts, `.` for
+/// part 1 only), followed by the part 2 completion times of each day measured
+/// from the puzzle unlock.
+pub fn print_leaderboard(leaderboard: &Leaderboard) {
+    let last_day = (0..25)
+        .rev()
+        .find(|&day| leaderboard.members.iter().any(|member| member.star_times[day][0].is_some()))
+        .map_or(0, |day| day + 1);
+
+    let name_width = leaderboard.members.iter().map(|member| member.name.chars().count()).max().unwrap_or(0);
+    let day_header = (1..=last_day).map(|day| char::from_digit(day as u32 % 10, 10).unwrap()).collect::<String>();
+    println!("{:>4} {:>5} {:>5}  {:<name_width$}  {}", "Rank", "Score", "Stars", "Name", day_header);
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let stars = (0..last_day).map(|day| day_stars(member, day)).collect::<String>();
+        println!("{:>4} {:>5} {:>5}  {:<name_width$}  {}", rank + 1, member.local_score, member.stars, member.name, stars);
+    }
+
+    for day in 0..last_day {
+        let unlock = unlock_time(leaderboard.year, day as u32 + 1);
+        let mut times =
+            leaderboard.members
+                .iter()
+                .filter_map(|member| {
+                    let time = UNIX_EPOCH + Duration::from_secs(member.star_times[day][1]?);
+                    Some((time.duration_since(unlock).unwrap_or_default(), &member.name))
+                })
+                .collect::<Vec<_>>();
+
+        if times.is_empty() {
+            continue;
+        }
+
+        times.sort();
+        println!();
+        println!("Day {} part 2:", day + 1);
+        for (time, name) in times {
+            println!("  {:>14}  {}", format_countdown(time), name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const LEADERBOARD: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/leaderboard.json"));
+
+    #[test]
+    fn parses_members_sorted_by_score_then_name() {
+        let leaderboard = parse_leaderboard(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year, 2022);
+
+        let names = leaderboard.members.iter().map(|member| member.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["(anonymous user #2002)", "alice", "böb \"the builder\"", "carol"]);
+
+        let scores = leaderboard.members.iter().map(|member| (member.local_score, member.stars)).collect::<Vec<_>>();
+        assert_eq!(scores, [(12, 2), (10, 3), (10, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn parses_star_times_per_day_and_part() {
+        let leaderboard = parse_leaderboard(LEADERBOARD).unwrap();
+        let alice = &leaderboard.members[1];
+        assert_eq!(alice.star_times[0], [Some(1669871000), Some(1669871600)]);
+        assert_eq!(alice.star_times[1], [Some(1669958000), None]);
+        assert!(alice.star_times[2..].iter().all(|times| *times == [None, None]));
+
+        assert_eq!(day_stars(alice, 0), '*');
+        assert_eq!(day_stars(alice, 1), '.');
+        assert_eq!(day_stars(alice, 2), ' ');
+        assert!(leaderboard.members[3].star_times.iter().all(|times| *times == [None, None]));
+    }
+
+    #[test]
+    fn rejects_leaderboards_without_year_or_members() {
+        assert!(parse_leaderboard(r#"{"members": {}}"#).is_err());
+        assert!(parse_leaderboard(r#"{"event": "2022"}"#).is_err());
+        assert!(parse_leaderboard(r#"{"event": "2022", "members": {"1": {"name": "x"}}}"#).is_err());
+        assert!(parse_leaderboard("<html>").is_err());
+    }
+
+}
@@ -1,85 +1,55 @@
 #![cfg_attr(test, allow(dead_code, unused_imports))]
 #![feature(random)]
 
+mod alloc;
+mod answers;
+mod config;
+mod error;
+mod history;
+mod journal;
+mod json;
+mod leaderboard;
+mod new_day;
+mod params;
+mod puzzle;
+mod report;
+mod runner;
 mod scaffold;
+mod submit;
+mod unlock;
 mod utils;
 
-use std::time::{Duration, Instant};
+use std::{ops::RangeInclusive, path::PathBuf, time::{Duration, Instant}};
 
-use crate::scaffold::{AocDay, AocResult, AocYear, get_input};
+use crate::answers::{get_answers, write_answers};
+use crate::config::{init_config, select_session_profile};
+use crate::history::{HistoryRun, append_run, load_baseline, load_runs, parse_baseline_name, print_comparison, save_baseline};
+use crate::journal::{JournalCheck, Verdict, append_journal, check_answer, load_journal};
+use crate::leaderboard::{get_leaderboard_json, parse_leaderboard, print_leaderboard};
+use crate::new_day::{InputType, create_day};
+use crate::params::{get_params, parse_param};
+use crate::report::{OutputFormat, Report};
+use crate::runner::{DayResult, RunOptions, install_panic_hook, run_parallel, run_solver};
+use crate::scaffold::{AocDay, AocResult, AocYear, DownloadError, PuzzleInput, get_input, get_input_profiles, get_input_root, get_profile_input, read_input};
+use crate::submit::{SubmitVerdict, submit_answer};
+use crate::unlock::set_wait_for_unlock;
 
 use clap::*;
-use scaffold::InputParser;
-
-aoc_solvers!{
-    year_2015 {
-        day_01,
-        day_02,
-        day_03,
-        day_04,
-        day_05,
-        day_06,
-        day_07,
-        day_08
-    },
-    year_2022 {
-        day_01,
-        day_02,
-        day_03,
-        day_04,
-        day_05,
-        day_06,
-        day_07,
-        day_08,
-        day_09,
-        day_10,
-        day_11,
-        day_12,
-        day_13,
-        day_14,
-        day_15,
-        day_16,
-        day_17,
-        day_18,
-        day_19,
-        day_20,
-        day_21,
-        day_22,
-        day_23,
-        day_24,
-        day_25
-    },
-    year_2024 {
-        day_01,
-        day_02,
-        day_03,
-        day_04,
-        day_05,
-        day_06,
-        day_07,
-        day_08,
-        day_09,
-        day_10,
-        day_11,
-        day_12,
-        day_13,
-        day_14,
-        day_15,
-        day_16,
-        day_17,
-        day_18,
-        day_19,
-        day_20,
-        day_21,
-        day_22,
-        day_23,
-        day_24,
-        day_25
-    }
-}
+
+// The `aoc_solvers!` invocation listing every `src/year_*/day_*.rs` module is
+// generated by `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/solvers.rs"));
 
 #[derive(Parser)]
 struct AocArgs {
+    /// Session profile from the config file to use, overrides AOC_SESSION_PROFILE
+    #[arg(long, global = true)]
+    session_profile: Option<String>,
+
+    /// Fail downloads of puzzles that aren't unlocked yet instead of waiting
+    #[arg(long, global = true)]
+    no_wait: bool,
+
     #[command(subcommand)]
     command: Option<AocCommand>
 }
@@ -87,7 +57,14 @@ struct AocArgs {
 #[derive(Subcommand)]
 enum AocCommand {
     List,
-    Run(RunCommand)
+    Run(RunCommand),
+    Journal(JournalCommand),
+    Verify(VerifyCommand),
+    Crosscheck(CrosscheckCommand),
+    Compare(CompareCommand),
+    New(NewCommand),
+    Leaderboard(LeaderboardCommand),
+    Submit(SubmitCommand)
 }
 
 impl Default for AocCommand {
@@ -98,79 +75,959 @@ impl Default for AocCommand {
 
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum NumberSelector {
+    #[default]
+    All,
+    Ranges(Vec<RangeInclusive<u32>>)
+}
+
+impl NumberSelector {
+
+    /// Checks a module name such as `year_2015` or `day_07` against the selector.
+    fn matches(&self, module_name: &str) -> bool {
+        match self {
+            NumberSelector::All => true,
+            NumberSelector::Ranges(ranges) => {
+                let number = module_name[module_name.find('_').map_or(0, |index| index + 1)..].parse::<u32>();
+                number.is_ok_and(|number| ranges.iter().any(|range| range.contains(&number)))
+            }
+        }
+    }
+
+}
+
+/// Parses selectors like `7`, `1-10`, `3,5,9`, `day_07`, `2015,2024` or `all`.
+fn parse_selector(value: &str) -> Result<NumberSelector, String> {
+    let parse_number = |token: &str| {
+        let token = token.trim();
+        let number = token.strip_prefix("year_").or_else(|| token.strip_prefix("day_")).unwrap_or(token);
+        number.parse::<u32>().map_err(|_| format!("invalid selector `{}`", token))
+    };
+
+    let mut ranges = vec![];
+    for token in value.split(',') {
+        if token.trim() == "all" {
+            return Ok(NumberSelector::All);
+        }
+
+        match token.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_number(from)?, parse_number(to)?);
+                if from > to {
+                    return Err(format!("invalid range `{}`, it starts after it ends", token.trim()));
+                }
+
+                ranges.push(from..=to);
+            },
+            None => {
+                let number = parse_number(token)?;
+                ranges.push(number..=number);
+            }
+        }
+    }
+
+    Ok(NumberSelector::Ranges(ranges))
+}
+
+#[derive(Args, Clone, Default)]
+struct DaySelection {
+    /// Years to run, e.g. `2024`, `2015,2022`, `year_2022` or `all`
+    #[arg(short, long, value_parser = parse_selector, default_value = "all")]
+    year: NumberSelector,
+
+    /// Days to run, e.g. `7`, `1-10`, `3,5,9`, `day_07` or `all`
+    #[arg(short, long, value_parser = parse_selector, default_value = "all")]
+    day: NumberSelector,
+
+    /// Only run the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    #[arg(short, long)]
+    example: bool,
+
+    /// Use the named input in `input/<year>/<day>/<profile>/` instead of the
+    /// default one, skipping days without it
+    #[arg(long, conflicts_with_all = ["example", "all_profiles"])]
+    profile: Option<String>,
+
+    /// Use the default input and then every input profile of each day
+    #[arg(long, conflicts_with = "example")]
+    all_profiles: bool,
+
+    /// Override a puzzle parameter, e.g. `width=11`, for the days declaring
+    /// it. Takes precedence over the params file of the input.
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, isize)>
+}
+
 #[derive(Args, Clone, Default)]
 struct RunCommand {
+    #[command(flatten)]
+    selection: DaySelection,
+
+    /// Output format for the results
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Number of timed runs of each part
+    #[arg(long, default_value_t = 1)]
+    repeat: usize,
+
+    /// Number of untimed runs of each part before timing starts
+    #[arg(long, default_value_t = 0)]
+    warmup: usize,
+
+    /// Give up on a part after this long, e.g. `30s`, `500ms` or `2m`
+    #[arg(long, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Number of days to run in parallel
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Read the input from this file (or `-` for stdin) instead of the input
+    /// directory. Requires selecting a single year and day.
+    #[arg(short, long, conflicts_with_all = ["example", "profile", "all_profiles"])]
+    input: Option<PathBuf>,
+
+    /// Report allocations and peak memory for each part. Requires building
+    /// with the `mem-stats` feature.
+    #[arg(long)]
+    mem: bool,
+
+    /// Don't record the timings of this run in the history file
+    #[arg(long)]
+    no_history: bool,
+
+    /// Directory holding the timing history, defaults to the input directory
+    #[arg(long)]
+    history_dir: Option<PathBuf>,
+
+    /// Also save the timings of this run as a named baseline for `compare`.
+    /// Only real inputs make comparable baselines.
+    #[arg(long, value_parser = parse_baseline_name, conflicts_with_all = ["example", "input"])]
+    save_baseline: Option<String>,
+
+    /// Run this variant of the parts that have it, e.g. `z3` or `naive`,
+    /// instead of the default implementation
+    #[arg(long)]
+    variant: Option<String>
+}
+
+impl RunCommand {
+
+    fn run_options(&self, variant: Option<&'static str>) -> RunOptions {
+        RunOptions {
+            repeat: self.repeat.max(1),
+            warmup: self.warmup,
+            timeout: self.timeout,
+            part: self.selection.part.map(usize::from),
+            variant,
+            measure_memory: self.mem
+        }
+    }
+
+}
+
+#[derive(Args, Clone, Default)]
+struct VerifyCommand {
+    #[command(flatten)]
+    selection: DaySelection,
+
+    /// Record the computed answers for parts that have no recorded answer yet
+    #[arg(long)]
+    record: bool
+}
+
+#[derive(Args, Clone, Default)]
+struct CrosscheckCommand {
+    #[command(flatten)]
+    selection: DaySelection,
+
+    /// Number of timed runs of each variant
+    #[arg(long, default_value_t = 1)]
+    repeat: usize,
+
+    /// Number of untimed runs of each variant before timing starts
+    #[arg(long, default_value_t = 0)]
+    warmup: usize,
+
+    /// Give up on a variant after this long, e.g. `30s`, `500ms` or `2m`
+    #[arg(long, value_parser = parse_duration)]
+    timeout: Option<Duration>
+}
+
+#[derive(Args, Clone)]
+struct CompareCommand {
+    /// Compare against this named baseline instead of the previous run
+    #[arg(short, long, value_parser = parse_baseline_name)]
+    baseline: Option<String>,
+
+    /// Percentage a part has to slow down by to be flagged
+    #[arg(short, long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Directory holding the timing history, defaults to the input directory
+    #[arg(long)]
+    history_dir: Option<PathBuf>
+}
+
+#[derive(Args, Clone)]
+struct NewCommand {
+    #[arg(short, long)]
+    year: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    /// Input type the generated `part1`/`part2` take, or `solver` for a `Solver`
+    #[arg(short, long, value_enum, default_value_t)]
+    input_type: InputType,
+
+    /// Download the puzzle input and examples after creating the day
+    #[arg(short, long)]
+    fetch: bool,
+
+    /// Also save a plain text copy of the puzzle description
+    #[arg(long, requires = "fetch")]
+    description: bool
+}
+
+#[derive(Args, Clone)]
+struct SubmitCommand {
+    #[arg(short, long)]
+    year: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Submit this answer instead of computing it with the solver
     #[arg(short, long)]
-    year: Option<String>,
+    answer: Option<String>,
 
-    #[arg(short, long, requires = "year")]
-    day: Option<String>,
+    /// Submit even if the journal says the answer is wrong
+    #[arg(long)]
+    force: bool
+}
+
+#[derive(Args, Clone)]
+struct LeaderboardCommand {
+    /// Id of the private leaderboard, the number in its URL
+    id: u64,
 
     #[arg(short, long)]
-    example: bool
+    year: u32,
+
+    /// Print the leaderboard JSON as received instead of a table
+    #[arg(long)]
+    json: bool
+}
+
+#[derive(Args, Clone)]
+struct JournalCommand {
+    #[arg(short, long)]
+    year: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Record an answer tried outside of `submit`, requires --part and --verdict
+    #[arg(short, long, requires_all = ["part", "verdict"])]
+    add: Option<String>,
+
+    #[arg(short, long, value_enum, requires = "add")]
+    verdict: Option<Verdict>
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split_index = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split_index);
+    let amount = amount.parse::<f64>().map_err(|_| format!("invalid duration `{}`", value))?;
+    let unit_secs = match unit {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 60.0 * 60.0,
+        _ => return Err(format!("invalid duration unit `{}`, expected ms, s, m or h", unit))
+    };
+
+    Duration::try_from_secs_f64(amount * unit_secs).map_err(|_| format!("duration `{}` is too long", value))
+}
+
+#[derive(Default)]
+struct VerifySummary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    recorded: usize
+}
+
+impl DaySelection {
+
+    fn selected_days<'a>(&self, aoc_years: &'a [AocYear]) -> Vec<(&'a AocYear, &'a AocDay)> {
+        let mut selected_days = vec![];
+        for aoc_year in aoc_years {
+            if !self.year.matches(aoc_year.year) {
+                continue;
+            }
+
+            for aoc_day in aoc_year.days {
+                if self.day.matches(aoc_day.day) {
+                    selected_days.push((aoc_year, aoc_day));
+                }
+            }
+        }
+
+        selected_days
+    }
+
+    /// The selected days along with the input profile to use for each run,
+    /// `None` being the default input.
+    fn selected_inputs<'a>(&self, aoc_years: &'a [AocYear]) -> Vec<(&'a AocYear, &'a AocDay, Option<String>)> {
+        let mut selected_inputs = vec![];
+        for (aoc_year, aoc_day) in self.selected_days(aoc_years) {
+            if let Some(profile) = &self.profile {
+                if get_input_profiles(aoc_year.year, aoc_day.day).contains(profile) {
+                    selected_inputs.push((aoc_year, aoc_day, Some(profile.clone())));
+                }
+
+                continue;
+            }
+
+            selected_inputs.push((aoc_year, aoc_day, None));
+            if self.all_profiles {
+                for profile in get_input_profiles(aoc_year.year, aoc_day.day) {
+                    selected_inputs.push((aoc_year, aoc_day, Some(profile)));
+                }
+            }
+        }
+
+        selected_inputs
+    }
+
+    /// Checks that every `--param` is declared by at least one selected day.
+    fn check_params(&self, selected_inputs: &[(&AocYear, &AocDay, Option<String>)]) -> Result<(), String> {
+        for (name, _) in &self.params {
+            if !selected_inputs.iter().any(|(_, aoc_day, _)| aoc_day.params.iter().any(|param| param.name == name)) {
+                return Err(format!("none of the selected days has a `{}` parameter", name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `--param` overrides that apply to a day.
+    fn day_params(&self, aoc_day: &AocDay) -> Vec<(String, isize)> {
+        self.params
+            .iter()
+            .filter(|(name, _)| aoc_day.params.iter().any(|param| param.name == name))
+            .cloned()
+            .collect()
+    }
+
+    fn load_input(&self, aoc_year: &AocYear, aoc_day: &AocDay, profile: Option<&str>) -> Result<PuzzleInput, DownloadError> {
+        let text = match profile {
+            Some(profile) => get_profile_input(aoc_year.year, aoc_day.day, profile),
+            None => get_input(aoc_year.year, aoc_day.day, self.example)?
+        };
+
+        let mut params = get_params(aoc_year.year, aoc_day.day, profile, self.example);
+        params.extend(self.day_params(aoc_day));
+
+        Ok(PuzzleInput {
+            text,
+            params
+        })
+    }
+
 }
 
-fn run_solver(aoc_day: &AocDay, input: String) -> Duration {
-    let part_1 = {
-        let start = Instant::now();
-        let answer = (aoc_day.part_1)(InputParser::new(&input));
-        let end = Instant::now();
+fn find_day(aoc_years: &[AocYear], year: u32, day: u32) -> Option<(&AocYear, &AocDay)> {
+    let aoc_year = aoc_years.iter().find(|aoc_year| aoc_year.year == format!("year_{}", year))?;
+    let aoc_day = aoc_year.days.iter().find(|aoc_day| aoc_day.day == format!("day_{:02}", day))?;
+
+    Some((aoc_year, aoc_day))
+}
 
-        (answer, end - start)
+/// Compares the answers of a run on the real input against the journal of
+/// answers already tried for the day.
+fn check_journal(result: &DayResult, report: &Report) {
+    let journal = load_journal(result.year, result.day);
+    if journal.is_empty() {
+        return;
+    }
+
+    for (part, part_result) in result.parts() {
+        let Some(answer) = part_result.outcome.answer() else {
+            continue;
+        };
+
+        let check = check_answer(&journal, part, &answer.to_string());
+        if check != JournalCheck::Unknown {
+            report.add_note(result, part, &check);
+        }
+    }
+}
+
+/// Compares the answers of a run on an input profile against the answers
+/// recorded for that profile, returning the number of wrong answers.
+fn check_profile_answers(result: &DayResult, report: &Report) -> usize {
+    let Some(profile) = result.profile.as_deref() else {
+        return 0;
     };
 
-    let part_2 = {
-        let start = Instant::now();
-        let answer = (aoc_day.part_2)(InputParser::new(&input));
-        let end = Instant::now();
+    let answers = get_answers(result.year, result.day, Some(profile), false);
+    let mut wrong_answers = 0;
+    for (part, part_result) in result.parts() {
+        let (Some(actual), Some(expected)) = (part_result.outcome.answer(), answers.get(part)) else {
+            continue;
+        };
+
+        if actual.to_string() == *expected {
+            report.add_note(result, part, &"PASS");
+
+        } else {
+            report.add_note(result, part, &format!("FAIL (expected {})", expected));
+            wrong_answers += 1;
+        }
+    }
 
-        (answer, end - start)
+    wrong_answers
+}
+
+fn verify_solver(aoc_year: &AocYear, aoc_day: &AocDay, profile: Option<String>, args: &VerifyCommand, report: &mut Report, summary: &mut VerifySummary) {
+    let example = args.selection.example;
+    let load_input = || args.selection.load_input(aoc_year, aoc_day, profile.as_deref());
+    let options = RunOptions {
+        part: args.selection.part.map(usize::from),
+        ..RunOptions::default()
     };
 
-    println!("  - {}:", aoc_day.day);
-    println!("    - Part 1 ({:?}) = {}", part_1.1, part_1.0);
-    println!("    - Part 2 ({:?}) = {}", part_2.1, part_2.0);
+    let mut result = run_solver(aoc_year.year, aoc_day, load_input, &options);
+    result.profile = profile;
+    report.add_day(&result);
+
+    let profile = result.profile.as_deref();
+    let mut answers = get_answers(aoc_year.year, aoc_day.day, profile, example);
+    let mut record_answers = false;
+    for (part, part_result) in result.parts() {
+        match part_result.outcome.result() {
+            Some(AocResult::NotApplicable) => {
+                println!("    - Part {}: N/A", part);
+                continue;
+            },
+            Some(AocResult::Unsolved) => {
+                println!("    - Part {}: UNSOLVED", part);
+                summary.missing += 1;
+                continue;
+            },
+            _ => {}
+        }
+
+        let Some(actual) = part_result.outcome.answer().map(AocResult::to_string) else {
+            println!("    - Part {}: FAIL (solver failed)", part);
+            summary.failed += 1;
+            continue;
+        };
+
+        match answers.get(part) {
+            Some(expected) if *expected == actual => {
+                println!("    - Part {}: PASS", part);
+                summary.passed += 1;
+            },
+            Some(expected) => {
+                println!("    - Part {}: FAIL (expected {})", part, expected);
+                summary.failed += 1;
+            },
+            None if args.record => {
+                println!("    - Part {}: RECORDED", part);
+                answers.set(part, actual);
+                record_answers = true;
+                summary.recorded += 1;
+            },
+            None => {
+                println!("    - Part {}: MISSING", part);
+                summary.missing += 1;
+            }
+        }
+    }
+
+    if record_answers {
+        if let Err(error) = write_answers(aoc_year.year, aoc_day.day, profile, example, &answers) {
+            panic!("Failed to record answers for {}-{}: {:?}", aoc_year.year, aoc_day.day, error);
+        }
+    }
+}
+
+/// Resolves `--variant` against the variants registered by the selected days,
+/// `default` standing for the default implementations.
+fn find_variant(selected_inputs: &[(&AocYear, &AocDay, Option<String>)], name: &str) -> Result<Option<&'static str>, String> {
+    if name == "default" {
+        return Ok(None);
+    }
+
+    let mut names = vec!["default"];
+    for (_, aoc_day, _) in selected_inputs {
+        for (_, variant) in aoc_day.variants() {
+            if variant == name {
+                return Ok(Some(variant));
+            }
+
+            if !names.contains(&variant) {
+                names.push(variant);
+            }
+        }
+    }
+
+    Err(format!("none of the selected days has a `{}` variant, expected one of: {}", name, names.join(", ")))
+}
+
+/// Runs the default implementation and every variant of each part of a day on
+/// the same input, returning `false` if any of them failed or disagreed with
+/// the default one.
+fn crosscheck_solver(aoc_year: &AocYear, aoc_day: &AocDay, profile: Option<String>, args: &CrosscheckCommand) -> bool {
+    let variants = aoc_day.variants();
+    let mut success = true;
+    for part in [1, 2] {
+        let part_variants = variants.iter().filter(|(variant_part, _)| *variant_part == part).map(|(_, name)| *name).collect::<Vec<_>>();
+        if part_variants.is_empty() || args.selection.part.is_some_and(|selected_part| usize::from(selected_part) != part) {
+            continue;
+        }
+
+        let mut default = None;
+        for variant in std::iter::once(None).chain(part_variants.into_iter().map(Some)) {
+            let options = RunOptions {
+                repeat: args.repeat.max(1),
+                warmup: args.warmup,
+                timeout: args.timeout,
+                part: Some(part),
+                variant,
+                ..RunOptions::default()
+            };
+
+            let load_input = || args.selection.load_input(aoc_year, aoc_day, profile.as_deref());
+            let mut result = run_solver(aoc_year.year, aoc_day, load_input, &options);
+            result.profile = profile.clone();
+            if default.is_none() {
+                println!("  - {} {} part {}:", aoc_year.year, result.name(), part);
+            }
+
+            // The parse of a `Solver` is the same for every variant, so only
+            // the part itself is timed against the default.
+            let (_, part_result) = result.parts().next().unwrap();
+            let name = variant.unwrap_or("default");
+            let Some(answer) = part_result.outcome.answer().map(AocResult::to_string) else {
+                println!("    - {} ({:?}) = {}", name, part_result.duration, part_result.outcome);
+                success = false;
+                continue;
+            };
+
+            match &default {
+                None => {
+                    println!("    - {} ({:?}) = {}", name, part_result.duration, answer);
+                    default = Some((answer, part_result.duration));
+                },
+                Some((default_answer, default_duration)) => {
+                    let speed = part_result.duration.as_secs_f64() / default_duration.as_secs_f64().max(f64::MIN_POSITIVE);
+                    let verdict = if answer == *default_answer { "" } else { " MISMATCH" };
+                    println!("    - {} ({:?}, {:.2}x default) = {}{}", name, part_result.duration, speed, answer, verdict);
+                    success &= verdict.is_empty();
+                }
+            }
+        }
+    }
 
-    part_1.1 + part_2.1
+    success
 }
 
 fn aoc_main(aoc_years: &[AocYear]) {
     let mut args = AocArgs::parse();
+    if let Some(profile) = args.session_profile.take() {
+        select_session_profile(profile);
+    }
+
+    set_wait_for_unlock(!args.no_wait);
+
+    // Load the config up front so a bad one is reported once, not per day.
+    if let Err(message) = init_config() {
+        AocArgs::command().error(clap::error::ErrorKind::InvalidValue, message).exit();
+    }
+
     match args.command.take().unwrap_or_default() {
         AocCommand::List => {
             for aoc_year in aoc_years {
                 println!("{}:", aoc_year.year);
                 for aoc_day in aoc_year.days {
-                    println!(" - {}", aoc_day.day);
+                    let variants =
+                        aoc_day.variants()
+                            .iter()
+                            .map(|(part, name)| format!("part {} {}", part, name))
+                            .collect::<Vec<_>>();
+
+                    let params =
+                        aoc_day.params
+                            .iter()
+                            .map(|param| format!("{} = {}", param.name, param.default))
+                            .collect::<Vec<_>>();
+
+                    let mut details = vec![];
+                    if !variants.is_empty() {
+                        details.push(format!("variants: {}", variants.join(", ")));
+                    }
+
+                    if !params.is_empty() {
+                        details.push(format!("params: {}", params.join(", ")));
+                    }
+
+                    if details.is_empty() {
+                        println!(" - {}", aoc_day.day);
+
+                    } else {
+                        println!(" - {} ({})", aoc_day.day, details.join("; "));
+                    }
                 }
             }
 
         },
         AocCommand::Run(args) => {
-            let mut total_duration = Duration::default();
-            for aoc_year in aoc_years {
-                if let Some(year) = &args.year {
-                    if aoc_year.year != year {
-                        continue;
+            if args.mem && !alloc::ENABLED {
+                AocArgs::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "--mem requires building with `--features mem-stats`")
+                    .exit();
+            }
+
+            let selected_inputs = args.selection.selected_inputs(aoc_years);
+            if let Err(message) = args.selection.check_params(&selected_inputs) {
+                AocArgs::command().error(clap::error::ErrorKind::InvalidValue, message).exit();
+            }
+
+            if args.input.is_some() && selected_inputs.len() != 1 {
+                AocArgs::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        format!("--input requires selecting exactly one day, but {} were selected", selected_inputs.len()))
+                    .exit();
+            }
+
+            let variant = match args.variant.as_deref().map(|name| find_variant(&selected_inputs, name)) {
+                Some(Ok(variant)) => variant,
+                Some(Err(message)) => AocArgs::command().error(clap::error::ErrorKind::InvalidValue, message).exit(),
+                None => None
+            };
+
+            let options = args.run_options(variant);
+            let mut report = Report::new(args.format);
+            install_panic_hook();
+
+            let start = Instant::now();
+
+            let run_day = |(aoc_year, aoc_day, profile): &(&AocYear, &AocDay, Option<String>)| {
+                let load_input = || match &args.input {
+                    Some(path) => Ok(PuzzleInput {
+                        text: read_input(path),
+                        params: args.selection.day_params(aoc_day)
+                    }),
+                    None => args.selection.load_input(aoc_year, aoc_day, profile.as_deref())
+                };
+
+                let mut result = run_solver(aoc_year.year, aoc_day, load_input, &options);
+                result.profile = profile.clone();
+                result
+            };
+
+            // Only the default input has a journal and a timing history, the
+            // other profiles are checked against their recorded answers.
+            let mut history_run = HistoryRun::new();
+            let mut wrong_answers = 0;
+            run_parallel(&selected_inputs, args.jobs, run_day, |result| {
+                report.add_day(&result);
+                if result.profile.is_some() {
+                    wrong_answers += check_profile_answers(&result, &report);
+                    return;
+                }
+
+                if !args.selection.example && args.input.is_none() {
+                    check_journal(&result, &report);
+                }
+
+                history_run.add_day(&result);
+            });
+
+            let success = report.finish(start.elapsed()) && wrong_answers == 0;
+            if wrong_answers != 0 {
+                eprintln!("{} answer(s) differ from the recorded answers of their input profile", wrong_answers);
+            }
+
+            // Timings of example or ad-hoc inputs aren't comparable with the
+            // real ones, so leave them out of the history.
+            let history_dir = args.history_dir.clone().unwrap_or_else(get_input_root);
+            if !args.no_history && !args.selection.example && args.input.is_none() && !history_run.records.is_empty() {
+                if let Err(error) = append_run(&history_dir, &history_run) {
+                    eprintln!("Failed to record timing history: {:?}", error);
+                }
+            }
+
+            if let Some(name) = &args.save_baseline {
+                if let Err(error) = save_baseline(&history_dir, name, &history_run) {
+                    eprintln!("Failed to save baseline {}: {:?}", name, error);
+                }
+            }
+
+            if !success {
+                std::process::exit(1);
+            }
+        },
+        AocCommand::Verify(args) => {
+            let mut report = Report::new(OutputFormat::Text);
+            let mut summary = VerifySummary::default();
+            install_panic_hook();
+
+            let selected_inputs = args.selection.selected_inputs(aoc_years);
+            if let Err(message) = args.selection.check_params(&selected_inputs) {
+                AocArgs::command().error(clap::error::ErrorKind::InvalidValue, message).exit();
+            }
+
+            let start = Instant::now();
+            for (aoc_year, aoc_day, profile) in selected_inputs {
+                verify_solver(aoc_year, aoc_day, profile, &args, &mut report, &mut summary);
+            }
+
+            report.finish(start.elapsed());
+
+            println!(
+                "Verify summary: {} passed, {} failed, {} missing, {} recorded",
+                summary.passed,
+                summary.failed,
+                summary.missing,
+                summary.recorded);
+
+            if summary.failed != 0 {
+                std::process::exit(1);
+            }
+        },
+        AocCommand::Crosscheck(args) => {
+            let selected_inputs = args.selection.selected_inputs(aoc_years);
+            if let Err(message) = args.selection.check_params(&selected_inputs) {
+                AocArgs::command().error(clap::error::ErrorKind::InvalidValue, message).exit();
+            }
+
+            install_panic_hook();
+
+            let mut checked_days = 0;
+            let mut success = true;
+            for (aoc_year, aoc_day, profile) in selected_inputs {
+                if !aoc_day.variants().is_empty() {
+                    checked_days += 1;
+                    success &= crosscheck_solver(aoc_year, aoc_day, profile, &args);
+                }
+            }
+
+            if checked_days == 0 {
+                eprintln!("None of the selected days has variants to cross-check");
+            }
+
+            if !success {
+                eprintln!("Variants failed or disagreed with the default implementation");
+                std::process::exit(1);
+            }
+        },
+        AocCommand::Journal(args) => {
+            let aoc_year = format!("year_{}", args.year);
+            let aoc_day = format!("day_{:02}", args.day);
+            if let (Some(answer), Some(part), Some(verdict)) = (&args.add, args.part, args.verdict) {
+                if let Err(error) = append_journal(&aoc_year, &aoc_day, usize::from(part), verdict, answer) {
+                    eprintln!("Failed to record the answer in the journal: {:?}", error);
+                    std::process::exit(1);
+                }
+            }
+
+            let journal = load_journal(&aoc_year, &aoc_day);
+            for entry in journal.iter().filter(|entry| args.part.is_none_or(|part| usize::from(part) == entry.part)) {
+                println!("Part {}: {} was {}", entry.part, entry.answer, entry.verdict);
+            }
+        },
+        AocCommand::Compare(args) => {
+            let history_dir = args.history_dir.unwrap_or_else(get_input_root);
+            let mut runs = load_runs(&history_dir);
+            let Some(current) = runs.pop() else {
+                eprintln!("No runs recorded in {:?}", history_dir);
+                std::process::exit(1);
+            };
+
+            let previous = match &args.baseline {
+                Some(name) => load_baseline(&history_dir, name),
+                None => runs.pop()
+            };
+
+            let Some(previous) = previous else {
+                eprintln!("Nothing to compare against, need a previous run or an existing baseline");
+                std::process::exit(1);
+            };
+
+            print_comparison(&previous, &current, args.threshold);
+        },
+        AocCommand::Submit(args) => {
+            let aoc_year = format!("year_{}", args.year);
+            let aoc_day = format!("day_{:02}", args.day);
+            let part = usize::from(args.part);
+            let answer = match args.answer {
+                Some(answer) => answer,
+                None => {
+                    let Some((aoc_year, aoc_day)) = find_day(aoc_years, args.year, args.day) else {
+                        eprintln!("No solver registered for {}-{}", aoc_year, aoc_day);
+                        std::process::exit(1);
+                    };
+
+                    let options = RunOptions {
+                        part: Some(part),
+                        ..RunOptions::default()
+                    };
+
+                    let input = match get_input(aoc_year.year, aoc_day.day, false) {
+                        Ok(input) => input,
+                        Err(error) => {
+                            eprintln!("Failed to get the input: {}", error);
+                            std::process::exit(1);
+                        }
+                    };
+
+                    install_panic_hook();
+                    let load_input = || Ok::<_, DownloadError>(input);
+                    let result = run_solver(aoc_year.year, aoc_day, load_input, &options);
+                    let (_, part_result) = result.parts().next().unwrap();
+                    match part_result.outcome.result() {
+                        Some(answer) if answer.is_answer() => answer.to_string(),
+                        Some(answer) => {
+                            eprintln!("Nothing to submit, the solver returned {}", answer);
+                            std::process::exit(1);
+                        },
+                        None => {
+                            eprintln!("Failed to compute the answer: {}", part_result.outcome);
+                            std::process::exit(1);
+                        }
                     }
                 }
+            };
 
-                println!("{}:", aoc_year.year);
-                for aoc_day in aoc_year.days {
-                    if let Some(day) = &args.day {
-                        if aoc_day.day != day {
-                            continue;
+            let check = check_answer(&load_journal(&aoc_year, &aoc_day), part, &answer);
+            if check.is_wrong() && !args.force {
+                eprintln!("Not submitting {}: {}", answer, check);
+                eprintln!("Use --force to submit anyway");
+                std::process::exit(1);
+            }
+
+            println!("Submitting {} for {}-{} part {}...", answer, aoc_year, aoc_day, part);
+            match submit_answer(&aoc_year, &aoc_day, part, &answer) {
+                Ok(verdict) => {
+                    println!("{}", verdict);
+                    if let Some(journal_verdict) = Verdict::from_submit_verdict(&verdict) {
+                        if let Err(error) = append_journal(&aoc_year, &aoc_day, part, journal_verdict, &answer) {
+                            eprintln!("Failed to record the answer in the journal: {:?}", error);
                         }
                     }
-                    
-                    total_duration +=
-                        run_solver(
-                            aoc_day,
-                            get_input(aoc_year.year, aoc_day.day, args.example));
+
+                    if verdict != SubmitVerdict::Correct {
+                        std::process::exit(1);
+                    }
+                },
+                Err(error) => {
+                    eprintln!("Failed to submit answer: {}", error);
+                    std::process::exit(1);
                 }
             }
+        },
+        AocCommand::Leaderboard(args) => {
+            let json = match get_leaderboard_json(args.year, args.id) {
+                Ok(json) => json,
+                Err(error) => {
+                    eprintln!("Failed to get leaderboard {}: {}", args.id, error);
+                    std::process::exit(1);
+                }
+            };
+
+            if args.json {
+                println!("{}", json);
+                return;
+            }
 
-            println!("Total duration {:?}", total_duration);
+            match parse_leaderboard(&json) {
+                Ok(leaderboard) => print_leaderboard(&leaderboard),
+                Err(error) => {
+                    eprintln!("Failed to parse leaderboard {}: {}", args.id, error);
+                    std::process::exit(1);
+                }
+            }
+        },
+        AocCommand::New(args) => {
+            if let Err(error) = create_day(args.year, args.day, args.input_type, args.fetch, args.description) {
+                eprintln!("Failed to create day: {}", error);
+                std::process::exit(1);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parses_selectors() {
+        assert_eq!(parse_selector("7"), Ok(NumberSelector::Ranges(vec![7..=7])));
+        assert_eq!(parse_selector("1-10"), Ok(NumberSelector::Ranges(vec![1..=10])));
+        assert_eq!(parse_selector("3,5,9"), Ok(NumberSelector::Ranges(vec![3..=3, 5..=5, 9..=9])));
+        assert_eq!(parse_selector("day_07"), Ok(NumberSelector::Ranges(vec![7..=7])));
+        assert_eq!(parse_selector("year_2022"), Ok(NumberSelector::Ranges(vec![2022..=2022])));
+        assert_eq!(parse_selector("2015, 2022-2024"), Ok(NumberSelector::Ranges(vec![2015..=2015, 2022..=2024])));
+        assert_eq!(parse_selector("all"), Ok(NumberSelector::All));
+        assert_eq!(parse_selector("5,all"), Ok(NumberSelector::All));
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        for value in ["", "x", "1-", "-3", "1-x", "day_x", "10-1"] {
+            assert!(parse_selector(value).is_err(), "{:?} should be rejected", value);
+        }
+
+        assert_eq!(parse_selector("5-5"), Ok(NumberSelector::Ranges(vec![5..=5])));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for value in ["", "s", "5d", "1..2s", "99999999999999999999h"] {
+            assert!(parse_duration(value).is_err(), "{:?} should be rejected", value);
+        }
+    }
+
+    #[test]
+    fn selectors_match_module_names() {
+        let selector = parse_selector("1-3,7").unwrap();
+        assert!(selector.matches("day_01"));
+        assert!(selector.matches("day_07"));
+        assert!(!selector.matches("day_04"));
+        assert!(parse_selector("2022").unwrap().matches("year_2022"));
+        assert!(NumberSelector::All.matches("day_25"));
+    }
+
+}
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::puzzle::download_examples;
+use crate::scaffold::{get_input, get_input_dir};
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum InputType {
+    #[default]
+    Str,
+    Parser,
+    Matrix,
+    Solver
+}
+
+impl InputType {
+
+    fn template(&self) -> &'static str {
+        match self {
+            InputType::Str => concat!(
+                "\n",
+                "pub fn part1(_input: &str) -> u64 {\n",
+                "    todo!()\n",
+                "}\n",
+                "\n",
+                "pub fn part2(_input: &str) -> u64 {\n",
+                "    todo!()\n",
+                "}\n"),
+            InputType::Parser => concat!(
+                "use crate::scaffold::InputParser;\n",
+                "\n",
+                "pub fn part1(mut _input: InputParser) -> u64 {\n",
+                "    todo!()\n",
+                "}\n",
+                "\n",
+                "pub fn part2(mut _input: InputParser) -> u64 {\n",
+                "    todo!()\n",
+                "}\n"),
+            InputType::Matrix => concat!(
+                "use crate::utils::Matrix2DBorrowed;\n",
+                "\n",
+                "pub fn part1(_grid: Matrix2DBorrowed<u8>) -> u64 {\n",
+                "    todo!()\n",
+                "}\n",
+                "\n",
+                "pub fn part2(_grid: Matrix2DBorrowed<u8>) -> u64 {\n",
+                "    todo!()\n",
+                "}\n"),
+            InputType::Solver => concat!(
+                "use crate::error::AocError;\n",
+                "use crate::scaffold::{InputParser, Solver};\n",
+                "\n",
+                "pub struct Solution;\n",
+                "\n",
+                "impl Solver for Solution {\n",
+                "    type Parsed = ();\n",
+                "    type Part1 = u64;\n",
+                "    type Part2 = u64;\n",
+                "\n",
+                "    fn parse(_input: InputParser) -> Result<Self::Parsed, AocError> {\n",
+                "        todo!()\n",
+                "    }\n",
+                "\n",
+                "    fn part1(_parsed: &Self::Parsed) -> Self::Part1 {\n",
+                "        todo!()\n",
+                "    }\n",
+                "\n",
+                "    fn part2(_parsed: &Self::Parsed) -> Self::Part2 {\n",
+                "        todo!()\n",
+                "    }\n",
+                "\n",
+                "}\n")
+        }
+    }
+
+}
+
+fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+// There's no need to register the new day anywhere, `build.rs` picks up every
+// `src/year_*/day_*.rs` file on the next build.
+
+pub fn create_day(year: u32, day: u32, input_type: InputType, fetch: bool, description: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let year = format!("year_{}", year);
+    let day = format!("day_{:02}", day);
+
+    let solver_path = source_dir().join(&year).join(format!("{}.rs", day));
+    if solver_path.exists() {
+        return Err(format!("{:?} already exists", solver_path).into());
+    }
+
+    std::fs::create_dir_all(solver_path.parent().unwrap())?;
+    std::fs::write(&solver_path, input_type.template())?;
+    println!("Created {:?}", solver_path);
+
+    let input_dir = get_input_dir(&year, &day);
+    std::fs::create_dir_all(&input_dir)?;
+    println!("Created {:?}", input_dir);
+
+    if fetch {
+        get_input(&year, &day, false)?;
+        if let Err(error) = download_examples(&year, &day, &input_dir, description) {
+            eprintln!("Failed to extract the examples from the puzzle page: {}", error);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use crate::scaffold::{AocParam, get_profile_dir};
+
+// Puzzle parameters that differ from their real-input defaults are stored
+// next to the input they apply to, one per line as `<name> = <value>`, e.g.
+// `example_params.txt` holding `width = 11` and `height = 7` for the example
+// of year_2024::day_14.
+
+pub fn get_params_path(aoc_year: &str, aoc_day: &str, profile: Option<&str>, example_input: bool) -> PathBuf {
+    let mut path = get_profile_dir(aoc_year, aoc_day, profile);
+    if example_input {
+        path.push("example_params.txt");
+
+    } else {
+        path.push("params.txt");
+    }
+
+    path
+}
+
+/// Parses an override like `width=11`.
+pub fn parse_param(value: &str) -> Result<(String, isize), String> {
+    let Some((name, param_value)) = value.split_once('=') else {
+        return Err(format!("invalid parameter `{}`, expected `name=value`", value));
+    };
+
+    let param_value = param_value.trim().parse::<isize>().map_err(|_| format!("invalid value for parameter `{}`", name.trim()))?;
+    Ok((name.trim().to_string(), param_value))
+}
+
+/// The parameters overridden for an input, an empty list if it has no params
+/// file.
+pub fn get_params(aoc_year: &str, aoc_day: &str, profile: Option<&str>, example_input: bool) -> Vec<(String, isize)> {
+    let path = get_params_path(aoc_year, aoc_day, profile, example_input);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| parse_param(line).unwrap_or_else(|error| panic!("Failed to read {:?}: {}", path, error)))
+        .collect()
+}
+
+/// Checks overrides against the parameters a day declares.
+pub fn check_params(params: &[(String, isize)], declared: &[AocParam]) -> Result<(), String> {
+    for (name, _) in params {
+        if !declared.iter().any(|param| param.name == name) {
+            let declared = declared.iter().map(|param| param.name).collect::<Vec<_>>();
+            return Err(format!("unknown parameter `{}`, expected one of: {}", name, declared.join(", ")));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::path::Path;
+
+use crate::scaffold::{DownloadError, build_client, check_status, get_puzzle_url};
+use crate::unlock::{SystemClock, should_wait_for_unlock, unlock_jitter, wait_for_unlock};
+
+const EXAMPLE_FILE: &str = "example.txt";
+const DESCRIPTION_FILE: &str = "puzzle.txt";
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Removes the tags from a piece of HTML and decodes the few entities the site
+/// uses, leaving the whitespace as is.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+/// Finds every `<start ...>...</end>` element of the HTML, returning the
+/// contents between the tags.
+fn find_elements<'a>(html: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut elements = Vec::new();
+    let mut rest = html;
+    while let Some(index) = rest.find(start) {
+        rest = &rest[index + start.len()..];
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+
+        let contents = &rest[open_end + 1..];
+        let close = contents.find(end).unwrap_or(contents.len());
+        elements.push(&contents[..close]);
+        rest = &contents[close..];
+    }
+
+    elements
+}
+
+/// Extracts the text of every `<pre><code>` block of a puzzle page, in page
+/// order. These hold the example inputs.
+pub fn extract_code_blocks(html: &str) -> Vec<String> {
+    find_elements(html, "<pre", "</pre>")
+        .into_iter()
+        .filter_map(|pre| {
+            let code = find_elements(pre, "<code", "</code>");
+            code.first().map(|code| strip_tags(code))
+        })
+        .collect()
+}
+
+/// Picks the block most likely to be the example input: the largest one,
+/// preferring the first of equally large blocks.
+pub fn main_example_index(blocks: &[String]) -> Option<usize> {
+    blocks
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, block)| block.len())
+        .map(|(index, _)| index)
+}
+
+/// Converts the `<article>`s of a puzzle page, one per part unlocked so far,
+/// into plain text.
+pub fn extract_description(html: &str) -> String {
+    let mut description = String::new();
+    for article in find_elements(html, "<article", "</article>") {
+        let article =
+            article
+                .replace("</h2>", "</h2>\n\n")
+                .replace("</p>", "</p>\n\n")
+                .replace("</pre>", "</pre>\n")
+                .replace("<li>", "<li>- ")
+                .replace("</li>", "</li>\n")
+                .replace("</ul>", "</ul>\n");
+
+        description += strip_tags(&article).trim();
+        description += "\n\n";
+    }
+
+    // The page has newlines of its own between the tags, so collapse the runs
+    // of blank lines that leaves.
+    while description.contains("\n\n\n") {
+        description = description.replace("\n\n\n", "\n\n");
+    }
+
+    description
+}
+
+fn download_puzzle_page(aoc_year: &str, aoc_day: &str) -> Result<String, DownloadError> {
+    wait_for_unlock(&SystemClock, aoc_year, aoc_day, should_wait_for_unlock(), unlock_jitter())?;
+
+    let puzzle_url = get_puzzle_url(aoc_year, aoc_day, "").map_err(|error| DownloadError::Request(error.to_string()))?;
+    let response = build_client()?.get(puzzle_url).send()?;
+    check_status(response.status().as_u16())?;
+
+    Ok(response.text()?)
+}
+
+/// Downloads the puzzle page and saves its code blocks to `dir`, the main one
+/// as `example.txt` and the others as `example_N.txt` where `N` is the
+/// position of the block on the page. Optionally saves the description too.
+pub fn download_examples(aoc_year: &str, aoc_day: &str, dir: &Path, save_description: bool) -> Result<(), DownloadError> {
+    let html = download_puzzle_page(aoc_year, aoc_day)?;
+    let blocks = extract_code_blocks(&html);
+    let Some(main_index) = main_example_index(&blocks) else {
+        return Err(DownloadError::NoExamples);
+    };
+
+    std::fs::create_dir_all(dir)?;
+    for (index, block) in blocks.iter().enumerate() {
+        let file_name = if index == main_index {
+            EXAMPLE_FILE.to_string()
+
+        } else {
+            format!("example_{}.txt", index + 1)
+        };
+
+        std::fs::write(dir.join(file_name), block)?;
+    }
+
+    if save_description {
+        std::fs::write(dir.join(DESCRIPTION_FILE), extract_description(&html))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const PUZZLE_PAGE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/puzzle_page.html"));
+
+    #[test]
+    fn extracts_code_blocks_in_page_order() {
+        let blocks = extract_code_blocks(PUZZLE_PAGE);
+        assert_eq!(blocks, [
+            "1000\n2000\n",
+            "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+            "7000\n8000\n9000\n"
+        ]);
+    }
+
+    #[test]
+    fn picks_the_largest_block_as_main_example() {
+        let blocks = extract_code_blocks(PUZZLE_PAGE);
+        assert_eq!(main_example_index(&blocks), Some(1));
+    }
+
+    #[test]
+    fn prefers_the_first_of_equally_large_blocks() {
+        let blocks = ["1\n", "2\n3\n", "4\n5\n"].map(String::from);
+        assert_eq!(main_example_index(&blocks), Some(1));
+        assert_eq!(main_example_index(&[]), None);
+    }
+
+    #[test]
+    fn extracts_the_description_of_both_parts() {
+        let description = extract_description(PUZZLE_PAGE);
+        assert!(description.starts_with("--- Day 1: Calorie Counting ---\n\nThe Elves take turns writing down the number of Calories in each item"));
+        assert!(description.contains("\n\n- The first Elf is carrying food with 1000, 2000, and 3000 Calories, a total of 6000 Calories.\n"));
+        assert!(description.contains("Note that a < b && b > c isn't \"code\"."));
+        assert!(description.contains("\n\n--- Part Two ---\n\nFind the top three Elves"));
+        assert!(description.ends_with("How many Calories are those Elves carrying in total?\n\n"));
+        assert!(!description.contains("Your puzzle answer"));
+        assert!(!description.contains("<em>") && !description.contains("<code>"));
+        assert!(!description.contains("\n\n\n"));
+    }
+
+}
@@ -0,0 +1,333 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::alloc::{MemStats, format_bytes};
+use crate::runner::{DayResult, PartOutcome, TimingStats};
+use crate::scaffold::AocResult;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv
+}
+
+pub struct Report {
+    format: OutputFormat,
+    current_year: Option<&'static str>,
+    json_records: Vec<String>,
+    failures: Vec<String>,
+    timed_out_parts: usize,
+    total_duration: Duration
+}
+
+fn escape_json(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c)
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+/// An answer as JSON: lists become arrays, anything else the string it's
+/// printed as, so large numbers survive JSON parsers using doubles.
+fn answer_json(answer: &AocResult) -> String {
+    match answer {
+        AocResult::List(values) => format!("[{}]", values.iter().map(answer_json).collect::<Vec<_>>().join(", ")),
+        answer => escape_json(&answer.to_string())
+    }
+}
+
+/// A row of the machine readable formats: the parsing of a `Solver` (without
+/// a `part`) or one of the parts.
+struct Record<'a> {
+    part: Option<usize>,
+    variant: Option<&'static str>,
+    status: &'static str,
+    answer: Option<&'a AocResult>,
+    error: Option<String>,
+    duration: Duration,
+    stats: &'a TimingStats,
+    consistent: bool,
+    memory: Option<MemStats>
+}
+
+impl Record<'_> {
+
+    fn stage(&self) -> &'static str {
+        if self.part.is_some() { "part" } else { "parse" }
+    }
+
+}
+
+fn records(result: &DayResult) -> Vec<Record<'_>> {
+    let parse = result.parse.iter().map(|parse| Record {
+        part: None,
+        variant: None,
+        status: parse.error.as_ref().map_or("ok", PartOutcome::status),
+        answer: None,
+        error: parse.error.as_ref().and_then(PartOutcome::error),
+        duration: parse.duration,
+        stats: &parse.stats,
+        consistent: true,
+        memory: parse.memory
+    });
+
+    let parts = result.parts().map(|(part, part_result)| Record {
+        part: Some(part),
+        variant: part_result.variant,
+        status: part_result.outcome.status(),
+        answer: part_result.outcome.result(),
+        error: part_result.outcome.error(),
+        duration: part_result.duration,
+        stats: &part_result.stats,
+        consistent: part_result.inconsistent_answer.is_none(),
+        memory: part_result.memory
+    });
+
+    parse.chain(parts).collect()
+}
+
+fn format_timing(stats: &TimingStats, duration: Duration, memory: Option<&MemStats>) -> String {
+    let mut timing = if stats.samples > 1 {
+        format!(
+            "median {:?}, min {:?}, mean {:?}, stddev {:?}, {} runs",
+            stats.median,
+            stats.min,
+            stats.mean,
+            stats.stddev,
+            stats.samples)
+
+    } else {
+        format!("{:?}", duration)
+    };
+
+    if let Some(memory) = memory {
+        timing += &format!(
+            ", {} allocs, {} allocated, {} peak",
+            memory.allocations,
+            format_bytes(memory.allocated_bytes),
+            format_bytes(memory.peak_bytes));
+    }
+
+    timing
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+
+    } else {
+        value.to_string()
+    }
+}
+
+impl Report {
+
+    pub fn new(format: OutputFormat) -> Self {
+        if format == OutputFormat::Csv {
+            println!("year,day,stage,part,status,type,answer,error,duration_ns,samples,min_ns,median_ns,mean_ns,stddev_ns,consistent,allocations,allocated_bytes,peak_bytes,profile,variant");
+        }
+
+        Self {
+            format,
+            current_year: None,
+            json_records: Vec::new(),
+            failures: Vec::new(),
+            timed_out_parts: 0,
+            total_duration: Duration::default()
+        }
+    }
+
+    pub fn add_day(&mut self, result: &DayResult) {
+        self.total_duration += result.duration();
+        self.timed_out_parts += result.timed_out_parts();
+        for (part, message) in result.failures() {
+            self.failures.push(format!("{} {} part {}: {}", result.year, result.name(), part, message));
+        }
+
+        match self.format {
+            OutputFormat::Text => {
+                if self.current_year != Some(result.year) {
+                    self.current_year = Some(result.year);
+                    println!("{}:", result.year);
+                }
+
+                println!("  - {}:", result.name());
+                if let Some(parse) = &result.parse {
+                    let timing = format_timing(&parse.stats, parse.duration, parse.memory.as_ref());
+                    match &parse.error {
+                        Some(error) => println!("    - Parse ({}) = {}", timing, error),
+                        None => println!("    - Parse ({})", timing)
+                    }
+                }
+
+                for (part, part_result) in result.parts() {
+                    let timing = format_timing(&part_result.stats, part_result.duration, part_result.memory.as_ref());
+                    match part_result.variant {
+                        Some(variant) => println!("    - Part {} [{}] ({}) = {}", part, variant, timing, part_result.outcome),
+                        None => println!("    - Part {} ({}) = {}", part, timing, part_result.outcome)
+                    }
+
+                    if let Some(inconsistent_answer) = &part_result.inconsistent_answer {
+                        println!("      WARNING: answer changed between runs (also saw {})", inconsistent_answer);
+                    }
+                }
+
+                if result.timed_out_parts() != 0 {
+                    println!(
+                        "    - Day total {:?} (excluding {} timed out part(s))",
+                        result.duration(),
+                        result.timed_out_parts());
+                }
+            },
+            OutputFormat::Json => {
+                for record in records(result) {
+                    let memory = record.memory;
+                    self.json_records.push(
+                        format!(
+                            "{{\"year\": {}, \"day\": {}, \"stage\": {}, \"part\": {}, \"status\": {}, \"type\": {}, \"answer\": {}, \"error\": {}, \"duration_ns\": {}, \"samples\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}, \"consistent\": {}, \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}, \"profile\": {}, \"variant\": {}}}",
+                            escape_json(result.year),
+                            escape_json(result.day),
+                            escape_json(record.stage()),
+                            record.part.map_or("null".to_string(), |part| part.to_string()),
+                            escape_json(record.status),
+                            record.answer.map_or("null".to_string(), |answer| escape_json(answer.type_name())),
+                            record.answer.filter(|answer| answer.is_answer()).map_or("null".to_string(), answer_json),
+                            record.error.map_or("null".to_string(), |error| escape_json(&error)),
+                            record.duration.as_nanos(),
+                            record.stats.samples,
+                            record.stats.min.as_nanos(),
+                            record.stats.median.as_nanos(),
+                            record.stats.mean.as_nanos(),
+                            record.stats.stddev.as_nanos(),
+                            record.consistent,
+                            memory.map_or("null".to_string(), |memory| memory.allocations.to_string()),
+                            memory.map_or("null".to_string(), |memory| memory.allocated_bytes.to_string()),
+                            memory.map_or("null".to_string(), |memory| memory.peak_bytes.to_string()),
+                            result.profile.as_deref().map_or("null".to_string(), escape_json),
+                            record.variant.map_or("null".to_string(), escape_json)));
+                }
+            },
+            OutputFormat::Csv => {
+                for record in records(result) {
+                    let memory = record.memory;
+                    println!(
+                        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                        result.year,
+                        result.day,
+                        record.stage(),
+                        record.part.map_or(String::new(), |part| part.to_string()),
+                        record.status,
+                        record.answer.map_or("", AocResult::type_name),
+                        escape_csv(&record.answer.filter(|answer| answer.is_answer()).map(AocResult::to_string).unwrap_or_default()),
+                        escape_csv(&record.error.unwrap_or_default()),
+                        record.duration.as_nanos(),
+                        record.stats.samples,
+                        record.stats.min.as_nanos(),
+                        record.stats.median.as_nanos(),
+                        record.stats.mean.as_nanos(),
+                        record.stats.stddev.as_nanos(),
+                        record.consistent,
+                        memory.map_or(String::new(), |memory| memory.allocations.to_string()),
+                        memory.map_or(String::new(), |memory| memory.allocated_bytes.to_string()),
+                        memory.map_or(String::new(), |memory| memory.peak_bytes.to_string()),
+                        escape_csv(result.profile.as_deref().unwrap_or_default()),
+                        record.variant.unwrap_or_default());
+                }
+            }
+        }
+    }
+
+    /// Prints a remark about a part of a day that was just added. Machine
+    /// readable formats keep stdout clean, so the remark goes to stderr.
+    pub fn add_note(&self, result: &DayResult, part: usize, note: &impl std::fmt::Display) {
+        if self.format == OutputFormat::Text {
+            println!("    - Part {}: {}", part, note);
+
+        } else {
+            eprintln!("{}-{} part {}: {}", result.year, result.name(), part, note);
+        }
+    }
+
+    /// Prints the totals and returns `false` if any part failed. `wall_time` is
+    /// the elapsed time of the whole run, which differs from the summed part
+    /// durations when days run in parallel.
+    pub fn finish(self, wall_time: Duration) -> bool {
+        match self.format {
+            OutputFormat::Text => {
+                if self.timed_out_parts != 0 {
+                    println!(
+                        "Total duration {:?} (excluding {} timed out part(s))",
+                        self.total_duration,
+                        self.timed_out_parts);
+
+                } else {
+                    println!("Total duration {:?}", self.total_duration);
+                }
+
+                println!("Wall time {:?}", wall_time);
+
+                if !self.failures.is_empty() {
+                    println!("{} part(s) failed:", self.failures.len());
+                    for failure in &self.failures {
+                        println!("  - {}", failure);
+                    }
+                }
+            },
+            OutputFormat::Json => {
+                println!("{{");
+                println!("  \"results\": [");
+                for (index, record) in self.json_records.iter().enumerate() {
+                    let separator = if index + 1 == self.json_records.len() { "" } else { "," };
+                    println!("    {}{}", record, separator);
+                }
+
+                println!("  ],");
+                println!(
+                    "  \"total\": {{\"duration_ns\": {}, \"wall_ns\": {}, \"failed\": {}, \"timed_out\": {}}}",
+                    self.total_duration.as_nanos(),
+                    wall_time.as_nanos(),
+                    self.failures.len(),
+                    self.timed_out_parts);
+                println!("}}");
+            },
+            OutputFormat::Csv => {
+                let summary = format!("{} failed, {} timed out", self.failures.len(), self.timed_out_parts);
+                println!("total,,,,,,,{},{},,,,,,,,,,,", escape_csv(&summary), self.total_duration.as_nanos());
+                println!("wall,,,,,,,,{},,,,,,,,,,,", wall_time.as_nanos());
+            }
+        }
+
+        self.failures.is_empty()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn formats_lists_as_json_arrays() {
+        let answer = AocResult::List(vec![AocResult::U64(3), AocResult::String("a\"b".to_string()), AocResult::List(vec![AocResult::I64(-1)])]);
+        assert_eq!(answer_json(&answer), r#"["3", "a\"b", ["-1"]]"#);
+        assert_eq!(answer_json(&AocResult::List(vec![])), "[]");
+        assert_eq!(answer_json(&AocResult::U128(u128::MAX)), format!("\"{}\"", u128::MAX));
+    }
+
+}
@@ -0,0 +1,575 @@
+use std::{cell::{Cell, RefCell}, collections::BTreeMap, fmt::Display, panic::AssertUnwindSafe, sync::{Arc, atomic::{AtomicUsize, Ordering}, mpsc}, thread, time::{Duration, Instant}};
+
+use crate::alloc::{self, MemStats};
+use crate::error::AocError;
+use crate::params::check_params;
+use crate::scaffold::{AocDay, AocResult, AocSolution, AocVariant, InputParser, ParsedInput, PuzzleInput};
+
+// Workers get a larger stack than the main thread's default on purpose, so
+// deeply recursive solvers don't overflow it when run with a timeout or jobs.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone, Copy)]
+pub struct RunOptions {
+    pub repeat: usize,
+    pub warmup: usize,
+    pub timeout: Option<Duration>,
+    pub part: Option<usize>,
+    /// Variant to run instead of the default implementation, for the parts
+    /// that have it.
+    pub variant: Option<&'static str>,
+    pub measure_memory: bool
+}
+
+impl Default for RunOptions {
+
+    fn default() -> Self {
+        Self {
+            repeat: 1,
+            warmup: 0,
+            timeout: None,
+            part: None,
+            variant: None,
+            measure_memory: false
+        }
+    }
+
+}
+
+impl RunOptions {
+
+    pub fn runs_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|selected_part| selected_part == part)
+    }
+
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct TimingStats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration
+}
+
+impl TimingStats {
+
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let count = samples.len();
+        let mean_secs = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count as f64;
+        let variance =
+            samples
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+                .sum::<f64>() / count as f64;
+
+        let median = if count.is_multiple_of(2) {
+            (samples[count / 2 - 1] + samples[count / 2]) / 2
+
+        } else {
+            samples[count / 2]
+        };
+
+        Self {
+            samples: count,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        }
+    }
+
+}
+
+pub enum PartOutcome {
+    Solved(AocResult),
+    /// The solver returned an error.
+    Error(AocError),
+    /// The solver panicked.
+    Failed(String),
+    TimedOut(Duration)
+}
+
+impl PartOutcome {
+
+    /// What the solver returned, including markers like `Unsolved`.
+    pub fn result(&self) -> Option<&AocResult> {
+        match self {
+            PartOutcome::Solved(result) => Some(result),
+            _ => None
+        }
+    }
+
+    /// The answer, if the solver returned one that can be checked.
+    pub fn answer(&self) -> Option<&AocResult> {
+        self.result().filter(|result| result.is_answer())
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            PartOutcome::Solved(AocResult::Unsolved) => "unsolved",
+            PartOutcome::Solved(_) => "ok",
+            PartOutcome::Error(_) => "error",
+            PartOutcome::Failed(_) => "failed",
+            PartOutcome::TimedOut(_) => "timeout"
+        }
+    }
+
+    pub fn error(&self) -> Option<String> {
+        match self {
+            PartOutcome::Solved(_) => None,
+            PartOutcome::Error(error) => Some(error.details()),
+            PartOutcome::Failed(message) => Some(message.clone()),
+            PartOutcome::TimedOut(timeout) => Some(format!("timed out after {:?}", timeout))
+        }
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, PartOutcome::TimedOut(_))
+    }
+
+}
+
+impl Display for PartOutcome {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartOutcome::Solved(answer) => write!(f, "{}", answer),
+            PartOutcome::Error(error) => write!(f, "ERROR: {}", error.details()),
+            PartOutcome::Failed(message) => write!(f, "FAILED: {}", message),
+            PartOutcome::TimedOut(timeout) => write!(f, "TIMEOUT (after {:?})", timeout)
+        }
+    }
+
+}
+
+pub struct PartResult {
+    pub outcome: PartOutcome,
+    /// `None` when the default implementation ran.
+    pub variant: Option<&'static str>,
+    pub duration: Duration,
+    pub stats: TimingStats,
+    pub memory: Option<MemStats>,
+    pub inconsistent_answer: Option<AocResult>
+}
+
+/// Timing of the parsing done by a `Solver` before running its parts.
+pub struct ParseResult {
+    pub error: Option<PartOutcome>,
+    pub duration: Duration,
+    pub stats: TimingStats,
+    pub memory: Option<MemStats>
+}
+
+pub struct DayResult {
+    pub year: &'static str,
+    pub day: &'static str,
+    /// The input profile the day ran on, `None` for the default input.
+    pub profile: Option<String>,
+    /// Only set for days with a `Solver`.
+    pub parse: Option<ParseResult>,
+    pub part_1: Option<PartResult>,
+    pub part_2: Option<PartResult>
+}
+
+impl DayResult {
+
+    /// The day, along with the input profile if it isn't the default input.
+    pub fn name(&self) -> String {
+        match &self.profile {
+            Some(profile) => format!("{} [{}]", self.day, profile),
+            None => self.day.to_string()
+        }
+    }
+
+    /// Iterates over the parts that were run.
+    pub fn parts(&self) -> impl Iterator<Item = (usize, &PartResult)> {
+        [(1, &self.part_1), (2, &self.part_2)]
+            .into_iter()
+            .filter_map(|(part, part_result)| part_result.as_ref().map(|part_result| (part, part_result)))
+    }
+
+    /// Total duration of the parsing and the parts that finished. Timed out
+    /// parts are left out since their duration only reflects the timeout.
+    pub fn duration(&self) -> Duration {
+        let parse_duration =
+            self.parse
+                .as_ref()
+                .filter(|parse| parse.error.as_ref().is_none_or(|error| !error.is_timed_out()))
+                .map_or(Duration::default(), |parse| parse.duration);
+
+        parse_duration +
+            self.parts()
+                .filter(|(_, part_result)| !part_result.outcome.is_timed_out())
+                .map(|(_, part_result)| part_result.duration)
+                .sum::<Duration>()
+    }
+
+    pub fn timed_out_parts(&self) -> usize {
+        self.parts()
+            .filter(|(_, part_result)| part_result.outcome.is_timed_out())
+            .count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        self.parts().filter_map(|(part, part_result)| {
+            part_result.outcome.error().map(|message| (part, message))
+        })
+    }
+
+}
+
+/// Wraps the default panic hook so that panics inside `catch_panic` stash their
+/// message for the report instead of printing a backtrace.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !CATCHING_PANICS.get() {
+            default_hook(info);
+            return;
+        }
+
+        let message = match info.payload().downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => match info.payload().downcast_ref::<String>() {
+                Some(message) => message.clone(),
+                None => "Box<dyn Any>".to_string()
+            }
+        };
+
+        let message = match info.location() {
+            Some(location) => format!("{} ({}:{})", message, location.file(), location.line()),
+            None => message
+        };
+
+        PANIC_MESSAGE.with(|panic_message| *panic_message.borrow_mut() = Some(message));
+    }));
+}
+
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let was_catching = CATCHING_PANICS.replace(true);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.set(was_catching);
+
+    result.map_err(|_| {
+        PANIC_MESSAGE
+            .with(|panic_message| panic_message.borrow_mut().take())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+struct TimedRun<T> {
+    value: Result<T, PartOutcome>,
+    duration: Duration,
+    memory: MemStats
+}
+
+fn time_run<T>(run: impl FnOnce() -> Result<T, AocError>) -> TimedRun<T> {
+    let ((value, duration), memory) = alloc::measure(|| {
+        let start = Instant::now();
+        let value = catch_panic(run);
+        let end = Instant::now();
+
+        (value, end - start)
+    });
+
+    TimedRun {
+        value: value.map_err(PartOutcome::Failed).and_then(|value| value.map_err(PartOutcome::Error)),
+        duration,
+        memory
+    }
+}
+
+/// Runs on a worker thread and gives up after `timeout`. There is no way to
+/// stop the worker, so a timed out run keeps going detached in the background
+/// until the process exits.
+fn time_run_with_timeout<T: Send + 'static>(run: impl FnOnce() -> Result<T, AocError> + Send + 'static, timeout: Duration) -> TimedRun<T> {
+    let (sender, receiver) = mpsc::channel();
+    let spawn_result =
+        thread::Builder::new()
+            .name("aoc-solver".to_string())
+            .stack_size(WORKER_STACK_SIZE)
+            .spawn(move || {
+                let _ = sender.send(time_run(run));
+            });
+
+    if let Err(error) = spawn_result {
+        return TimedRun {
+            value: Err(PartOutcome::Failed(format!("failed to spawn solver thread: {}", error))),
+            duration: Duration::default(),
+            memory: MemStats::default()
+        };
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(_) => TimedRun {
+            value: Err(PartOutcome::TimedOut(timeout)),
+            duration: timeout,
+            memory: MemStats::default()
+        }
+    }
+}
+
+fn failed_part(outcome: PartOutcome, duration: Duration) -> PartResult {
+    PartResult {
+        outcome,
+        variant: None,
+        duration,
+        stats: TimingStats::from_samples(vec![duration]),
+        memory: None,
+        inconsistent_answer: None
+    }
+}
+
+struct RepeatedRun<T> {
+    value: T,
+    stats: TimingStats,
+    memory: Option<MemStats>
+}
+
+/// Runs `run` with the warmup and repeats of `options`, keeping the value of
+/// the first timed run. Every later value is passed to `on_repeat` along with
+/// the first one.
+fn run_repeated<T: Send + 'static>(
+    run: impl FnOnce() -> Result<T, AocError> + Clone + Send + 'static,
+    options: &RunOptions,
+    mut on_repeat: impl FnMut(&T, T)) -> Result<RepeatedRun<T>, (PartOutcome, Duration)> {
+
+    let run_once = || match options.timeout {
+        Some(timeout) => time_run_with_timeout(run.clone(), timeout),
+        None => time_run(run.clone())
+    };
+
+    for _ in 0..options.warmup {
+        let run = run_once();
+        if let Err(outcome) = run.value {
+            return Err((outcome, run.duration));
+        }
+    }
+
+    let mut first = None;
+    let mut samples = vec![];
+    for _ in 0..options.repeat.max(1) {
+        let run = run_once();
+        let value = match run.value {
+            Ok(value) => value,
+            Err(outcome) => return Err((outcome, run.duration))
+        };
+
+        samples.push(run.duration);
+        match &first {
+            None => first = Some((value, options.measure_memory.then_some(run.memory))),
+            Some((first, _)) => on_repeat(first, value)
+        }
+    }
+
+    let (value, memory) = first.unwrap();
+    Ok(RepeatedRun {
+        value,
+        stats: TimingStats::from_samples(samples),
+        memory
+    })
+}
+
+fn run_part(part: impl FnOnce() -> Result<AocResult, AocError> + Clone + Send + 'static, options: &RunOptions) -> PartResult {
+    let mut inconsistent_answer = None;
+    let check_answer = |answer: &AocResult, repeat_answer: AocResult| {
+        if *answer != repeat_answer && inconsistent_answer.is_none() {
+            inconsistent_answer = Some(repeat_answer);
+        }
+    };
+
+    match run_repeated(part, options, check_answer) {
+        Ok(run) => PartResult {
+            outcome: PartOutcome::Solved(run.value),
+            variant: None,
+            duration: run.stats.median,
+            stats: run.stats,
+            memory: run.memory,
+            inconsistent_answer
+        },
+        Err((outcome, duration)) => failed_part(outcome, duration)
+    }
+}
+
+/// Parses the input of a `Solver`, returning the timing of the parse and the
+/// parsed input if it succeeded.
+fn run_parse(parse: fn(InputParser) -> Result<ParsedInput, AocError>, input: &Arc<str>, params: &Arc<[(String, isize)]>, options: &RunOptions) -> (ParseResult, Option<ParsedInput>) {
+    let (input, params) = (input.clone(), params.clone());
+    match run_repeated(move || parse(InputParser::with_params(&input, &params)), options, |_, _| {}) {
+        Ok(run) => {
+            let parse_result = ParseResult {
+                error: None,
+                duration: run.stats.median,
+                stats: run.stats,
+                memory: run.memory
+            };
+
+            (parse_result, Some(run.value))
+        },
+        Err((outcome, duration)) => {
+            let parse_result = ParseResult {
+                error: Some(outcome),
+                duration,
+                stats: TimingStats::from_samples(vec![duration]),
+                memory: None
+            };
+
+            (parse_result, None)
+        }
+    }
+}
+
+/// The outcome of the parts when the input couldn't be parsed.
+fn parse_failure(error: &PartOutcome) -> PartOutcome {
+    match error {
+        PartOutcome::TimedOut(timeout) => PartOutcome::TimedOut(*timeout),
+        error => PartOutcome::Failed(format!("failed to parse input: {}", error.error().unwrap_or_default()))
+    }
+}
+
+/// The implementation of `part` to run: the variant selected in `options` if
+/// the day has it for that part, else the default one.
+fn select_variant<F: Copy>(default: F, variants: &[AocVariant<F>], part: usize, options: &RunOptions) -> (F, Option<&'static str>) {
+    options.variant
+        .and_then(|name| variants.iter().find(|variant| variant.part == part && variant.name == name))
+        .map_or((default, None), |variant| (variant.solve, Some(variant.name)))
+}
+
+pub fn run_solver<I: Into<PuzzleInput>, E: Display>(aoc_year: &'static str, aoc_day: &AocDay, load_input: impl FnOnce() -> Result<I, E>, options: &RunOptions) -> DayResult {
+    let loaded_input =
+        catch_panic(load_input)
+            .and_then(|input| input.map(Into::into).map_err(|error| error.to_string()))
+            .and_then(|input: PuzzleInput| check_params(&input.params, aoc_day.params).map(|_| input));
+
+    let (input, params) = match loaded_input {
+        Ok(input) => (Arc::<str>::from(input.text), Arc::<[(String, isize)]>::from(input.params)),
+        Err(message) => {
+            let message = format!("failed to load input: {}", message);
+            return DayResult {
+                year: aoc_year,
+                day: aoc_day.day,
+                profile: None,
+                parse: None,
+                part_1: options.runs_part(1).then(|| failed_part(PartOutcome::Failed(message.clone()), Duration::default())),
+                part_2: options.runs_part(2).then(|| failed_part(PartOutcome::Failed(message.clone()), Duration::default()))
+            };
+        }
+    };
+
+    let (parse, part_1, part_2) = match aoc_day.solution {
+        AocSolution::Parts { part_1, part_2, variants } => {
+            let run_with_input = |part: usize, default: fn(InputParser) -> Result<AocResult, AocError>| {
+                let (solve, variant) = select_variant(default, variants, part, options);
+                let (input, params) = (input.clone(), params.clone());
+                let mut part_result = run_part(move || solve(InputParser::with_params(&input, &params)), options);
+                part_result.variant = variant;
+                part_result
+            };
+
+            (
+                None,
+                options.runs_part(1).then(|| run_with_input(1, part_1)),
+                options.runs_part(2).then(|| run_with_input(2, part_2))
+            )
+        },
+        AocSolution::Solver { parse, part_1, part_2, variants } => {
+            let (parse_result, parsed) = run_parse(parse, &input, &params, options);
+            let run_with_parsed = |part: usize, default: fn(&ParsedInput) -> Result<AocResult, AocError>| {
+                let (solve, variant) = select_variant(default, variants, part, options);
+                let mut part_result = match &parsed {
+                    Some(parsed) => {
+                        let parsed = parsed.clone();
+                        run_part(move || solve(&parsed), options)
+                    },
+                    None => failed_part(parse_failure(parse_result.error.as_ref().unwrap()), Duration::default())
+                };
+
+                part_result.variant = variant;
+                part_result
+            };
+
+            let part_1 = options.runs_part(1).then(|| run_with_parsed(1, part_1));
+            let part_2 = options.runs_part(2).then(|| run_with_parsed(2, part_2));
+            (Some(parse_result), part_1, part_2)
+        }
+    };
+
+    DayResult {
+        year: aoc_year,
+        day: aoc_day.day,
+        profile: None,
+        parse,
+        part_1,
+        part_2
+    }
+}
+
+/// Runs `run_item` for every item on `jobs` worker threads, handing the results
+/// to `on_result` in the same order as `items` as soon as they are available.
+pub fn run_parallel<T: Sync, R: Send>(items: &[T], jobs: usize, run_item: impl Fn(&T) -> R + Sync, mut on_result: impl FnMut(R)) {
+    if jobs <= 1 {
+        for item in items {
+            on_result(run_item(item));
+        }
+
+        return;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+            let run_item = &run_item;
+            thread::Builder::new()
+                .name("aoc-job".to_string())
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, move || loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    if index >= items.len() || sender.send((index, run_item(&items[index]))).is_err() {
+                        break;
+                    }
+                })
+                .expect("Failed to spawn job thread");
+        }
+
+        drop(sender);
+
+        let mut pending_results = BTreeMap::new();
+        let mut next_result = 0;
+        for (index, result) in receiver {
+            pending_results.insert(index, result);
+            while let Some(result) = pending_results.remove(&next_result) {
+                on_result(result);
+                next_result += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn unsolved_parts_have_their_own_status() {
+        assert_eq!(PartOutcome::Solved(AocResult::U64(1)).status(), "ok");
+        assert_eq!(PartOutcome::Solved(AocResult::NotApplicable).status(), "ok");
+        assert_eq!(PartOutcome::Solved(AocResult::Unsolved).status(), "unsolved");
+        assert_eq!(PartOutcome::Failed("boom".to_string()).status(), "failed");
+        assert_eq!(PartOutcome::TimedOut(Duration::from_secs(1)).status(), "timeout");
+    }
+
+}
@@ -1,8 +1,12 @@
 
-use std::{fmt::Display, path::Path, sync::Arc};
+use std::{any::Any, fmt::Display, io::Read, path::{Path, PathBuf}, sync::Arc, time::Duration};
 
-use reqwest::{blocking::ClientBuilder, cookie::Jar, Url};
+use reqwest::{blocking::{Client, ClientBuilder}, cookie::Jar, Url};
 
+use crate::config::config;
+use crate::error::{AocError, IntoAocResult};
+use crate::puzzle::download_examples;
+use crate::unlock::{SystemClock, format_countdown, should_wait_for_unlock, unlock_jitter, wait_for_unlock};
 use crate::utils::{Matrix2DBorrowed, Vector2};
 
 pub struct AocYear {
@@ -12,29 +16,161 @@ pub struct AocYear {
 
 pub struct AocDay {
     pub day: &'static str,
-    pub part_1: fn(InputParser) -> AocResult,
-    pub part_2: fn(InputParser) -> AocResult
+    pub solution: AocSolution,
+    pub params: &'static [AocParam]
 }
 
+/// A constant of a puzzle that differs between the examples and the real
+/// inputs, like the size of a grid. A day declares its parameters as
+/// `pub const PARAMS: &[AocParam]` and reads them with `InputParser::param`,
+/// which falls back to the real-input default unless the input overrides it.
+#[derive(Clone, Copy)]
+pub struct AocParam {
+    pub name: &'static str,
+    pub default: isize
+}
+
+impl AocParam {
+
+    pub const fn new(name: &'static str, default: isize) -> Self {
+        Self {
+            name,
+            default
+        }
+    }
+
+}
+
+/// An input to run a day on, along with the parameters overridden for it.
+pub struct PuzzleInput {
+    pub text: String,
+    pub params: Vec<(String, isize)>
+}
+
+impl From<String> for PuzzleInput {
+
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            params: Vec::new()
+        }
+    }
+
+}
+
+/// The input as parsed by a `Solver`, shared between both parts.
+pub type ParsedInput = Arc<dyn Any + Send + Sync>;
+
+pub type PartFn = fn(InputParser) -> Result<AocResult, AocError>;
+pub type ParsedPartFn = fn(&ParsedInput) -> Result<AocResult, AocError>;
+
+/// An alternative implementation of a part, like `part2_z3` next to `part2`.
+/// It's registered under the name after the `partN_` prefix, `part1` and
+/// `part2` themselves being the `default` variant.
+pub struct AocVariant<F> {
+    pub name: &'static str,
+    pub part: usize,
+    pub solve: F
+}
+
+pub enum AocSolution {
+    /// `part1` and `part2` functions, each taking the raw input.
+    Parts {
+        part_1: PartFn,
+        part_2: PartFn,
+        variants: &'static [AocVariant<PartFn>]
+    },
+    /// A `Solver`, which parses the input once for both parts. Its variants
+    /// are associated functions of the solver type taking the parsed input.
+    Solver {
+        parse: fn(InputParser) -> Result<ParsedInput, AocError>,
+        part_1: ParsedPartFn,
+        part_2: ParsedPartFn,
+        variants: &'static [AocVariant<ParsedPartFn>]
+    }
+}
+
+impl AocDay {
+
+    /// The part and name of each variant besides the default ones.
+    pub fn variants(&self) -> Vec<(usize, &'static str)> {
+        match &self.solution {
+            AocSolution::Parts { variants, .. } => variants.iter().map(|variant| (variant.part, variant.name)).collect(),
+            AocSolution::Solver { variants, .. } => variants.iter().map(|variant| (variant.part, variant.name)).collect()
+        }
+    }
+
+}
+
+/// Alternative to plain `part1`/`part2` functions for days where both parts
+/// work on the same parsed input, so the parsing is only done (and timed)
+/// once. The parsed input is shared with the worker threads running the
+/// parts, so it can't borrow from the input.
+pub trait Solver {
+    type Parsed: Send + Sync + 'static;
+    type Part1: IntoAocResult;
+    type Part2: IntoAocResult;
+
+    fn parse(input: InputParser) -> Result<Self::Parsed, AocError>;
+    fn part1(parsed: &Self::Parsed) -> Self::Part1;
+    fn part2(parsed: &Self::Parsed) -> Self::Part2;
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum AocResult {
     I64(i64),
     U64(u64),
+    I128(i128),
+    U128(u128),
+    Bool(bool),
     String(String),
-    Vector2(Vector2)
+    Vector2(Vector2),
+    List(Vec<AocResult>),
+    /// The part has no puzzle to solve, like part 2 of day 25.
+    NotApplicable,
+    /// The part hasn't been solved (yet).
+    Unsolved
 }
 
 #[derive(Clone, Copy)]
 pub struct InputParser<'a> {
-    input_str: &'a [u8]
+    input_str: &'a [u8],
+    full_input: &'a [u8],
+    params: &'a [(String, isize)]
 }
 
 impl<'a> InputParser<'a> {
 
     pub fn new(input_str: &'a str) -> Self {
+        Self::with_params(input_str, &[])
+    }
+
+    pub fn with_params(input_str: &'a str, params: &'a [(String, isize)]) -> Self {
         Self {
-            input_str: input_str.as_bytes()
+            input_str: input_str.as_bytes(),
+            full_input: input_str.as_bytes(),
+            params
         }
     }
+
+    /// The value of a puzzle parameter for this input, the last override
+    /// winning if there are several.
+    pub fn param(&self, param: AocParam) -> isize {
+        self.params
+            .iter()
+            .rfind(|(name, _)| name == param.name)
+            .map_or(param.default, |&(_, value)| value)
+    }
+
+    /// An error pointing at the part of the input that hasn't been parsed yet.
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        let offset = self.full_input.len() - self.input_str.len();
+        let parsed = &self.full_input[..offset];
+        let line_start = parsed.iter().rposition(|&c| c == b'\n').map_or(0, |index| index + 1);
+        let line = parsed.iter().filter(|&&c| c == b'\n').count() + 1;
+
+        AocError::new(message).at(line, offset - line_start + 1)
+    }
     
     pub fn next_int(&mut self) -> Option<isize> {
         loop {
@@ -156,6 +292,30 @@ impl From<u64> for AocResult {
 
 }
 
+impl From<i128> for AocResult {
+
+    fn from(inner: i128) -> Self {
+        AocResult::I128(inner)
+    }
+
+}
+
+impl From<u128> for AocResult {
+
+    fn from(inner: u128) -> Self {
+        AocResult::U128(inner)
+    }
+
+}
+
+impl From<bool> for AocResult {
+
+    fn from(inner: bool) -> Self {
+        AocResult::Bool(inner)
+    }
+
+}
+
 impl From<String> for AocResult {
 
     fn from(inner: String) -> Self {
@@ -172,61 +332,307 @@ impl From<Vector2> for AocResult {
 
 }
 
+impl<T: Into<AocResult>> From<Vec<T>> for AocResult {
+
+    fn from(inner: Vec<T>) -> Self {
+        AocResult::List(inner.into_iter().map(Into::into).collect())
+    }
+
+}
+
+impl AocResult {
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AocResult::I64(_) => "i64",
+            AocResult::U64(_) => "u64",
+            AocResult::I128(_) => "i128",
+            AocResult::U128(_) => "u128",
+            AocResult::Bool(_) => "bool",
+            AocResult::String(_) => "string",
+            AocResult::Vector2(_) => "vector2",
+            AocResult::List(_) => "list",
+            AocResult::NotApplicable => "not_applicable",
+            AocResult::Unsolved => "unsolved"
+        }
+    }
+
+    /// Whether this is an actual answer, something that can be checked or
+    /// submitted, rather than a marker for a part without one.
+    pub fn is_answer(&self) -> bool {
+        !matches!(self, AocResult::NotApplicable | AocResult::Unsolved)
+    }
+
+}
+
 impl Display for AocResult {
 
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AocResult::I64(val) => write!(f, "{}", val),
             AocResult::U64(val) => write!(f, "{}", val),
+            AocResult::I128(val) => write!(f, "{}", val),
+            AocResult::U128(val) => write!(f, "{}", val),
+            AocResult::Bool(val) => write!(f, "{}", val),
             AocResult::String(val) => write!(f, "{}", val),
-            AocResult::Vector2(val) => write!(f, "{},{}", val.x(), val.y())
+            AocResult::Vector2(val) => write!(f, "{},{}", val.x(), val.y()),
+            AocResult::List(values) => {
+                for (i, val) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+
+                    // Nested lists keep their brackets, or `[[1, 2], [3]]`
+                    // would print the same as `[1, 2, 3]`.
+                    match val {
+                        AocResult::List(_) => write!(f, "[{}]", val)?,
+                        _ => write!(f, "{}", val)?
+                    }
+                }
+
+                Ok(())
+            },
+            AocResult::NotApplicable => write!(f, "—"),
+            AocResult::Unsolved => write!(f, "unsolved")
         }
     }
 
 }
 
-fn download_input(aoc_year: &str, aoc_day: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let year_num = &aoc_year["year_".len()..];
-    let day_num = if aoc_day.starts_with("day_0") {
-        &aoc_day["day_0".len()..]
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Parses a base URL so that pages join onto it: without a trailing `/` the
+/// last segment of its path would be replaced, e.g. `/aoc` of a mock server.
+fn parse_base_url(base_url: &str) -> Result<Url, Box<dyn std::error::Error>> {
+    let mut url = Url::parse(base_url)?;
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+
+    Ok(url)
+}
+
+/// Base URL of the Advent of Code site, overridable with `AOC_BASE_URL` to
+/// point at a mock server.
+pub fn get_base_url() -> Result<Url, Box<dyn std::error::Error>> {
+    let base_url = std::env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+    parse_base_url(&base_url)
+}
+
+pub fn year_number(aoc_year: &str) -> &str {
+    aoc_year.strip_prefix("year_").unwrap_or(aoc_year)
+}
+
+pub fn day_number(aoc_day: &str) -> &str {
+    let day = aoc_day.strip_prefix("day_").unwrap_or(aoc_day);
+    day.strip_prefix('0').unwrap_or(day)
+}
+
+/// Builds the URL of a page of a puzzle, e.g. `input` or `answer`. An empty
+/// `page` gives the puzzle description itself.
+pub fn get_puzzle_url(aoc_year: &str, aoc_day: &str, page: &str) -> Result<Url, Box<dyn std::error::Error>> {
+    Ok(get_base_url()?.join(&puzzle_path(aoc_year, aoc_day, page))?)
+}
+
+fn puzzle_path(aoc_year: &str, aoc_day: &str, page: &str) -> String {
+    let path = format!("{}/day/{}", year_number(aoc_year), day_number(aoc_day));
+    if page.is_empty() {
+        path
 
     } else {
-        &aoc_day["day_".len()..]
-    };
+        format!("{}/{}", path, page)
+    }
+}
+
+/// Why downloading something from the Advent of Code site failed. Nothing is
+/// written to disk when a download fails.
+#[derive(Debug)]
+pub enum DownloadError {
+    MissingSessionToken(PathBuf),
+    BadSessionToken,
+    PuzzleLocked,
+    NotUnlocked(Duration),
+    RateLimited,
+    UnexpectedStatus(u16),
+    UnexpectedBody,
+    NoExamples,
+    Request(String),
+    Io(std::io::Error)
+}
+
+impl Display for DownloadError {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadError::MissingSessionToken(path) => write!(f, "no session token found, set AOC_SESSION, add it to the config file or copy the `session` cookie of the site to {:?}", path),
+            DownloadError::BadSessionToken => write!(f, "the session token was rejected, it has probably expired: log in again and update the token"),
+            DownloadError::PuzzleLocked => write!(f, "the puzzle isn't unlocked yet (puzzles unlock at midnight EST) or doesn't exist"),
+            DownloadError::NotUnlocked(remaining) => write!(f, "the puzzle unlocks in {} (midnight EST), try again then", format_countdown(*remaining)),
+            DownloadError::RateLimited => write!(f, "the site is rate limiting requests, wait a while before trying again"),
+            DownloadError::UnexpectedStatus(status) => write!(f, "the site responded with HTTP status {}", status),
+            DownloadError::UnexpectedBody => write!(f, "the site responded with an HTML page instead of the puzzle input"),
+            DownloadError::NoExamples => write!(f, "no code blocks found on the puzzle page"),
+            DownloadError::Request(error) => write!(f, "the request failed: {}", error),
+            DownloadError::Io(error) => write!(f, "{}", error)
+        }
+    }
+
+}
+
+impl std::error::Error for DownloadError {}
+
+impl From<reqwest::Error> for DownloadError {
+
+    fn from(error: reqwest::Error) -> Self {
+        DownloadError::Request(error.to_string())
+    }
+
+}
 
-    let mut session_token_file = std::env::current_dir()?;
-    session_token_file.push("input");
-    session_token_file.push("session-token.txt");
-    if !session_token_file.is_file() {
-        panic!("Failed to find session token file");
+impl From<std::io::Error> for DownloadError {
+
+    fn from(error: std::io::Error) -> Self {
+        DownloadError::Io(error)
+    }
+
+}
+
+/// Maps the status of a response from the site to an error.
+pub fn check_status(status: u16) -> Result<(), DownloadError> {
+    match status {
+        200..=299 => Ok(()),
+        400 | 401 | 403 => Err(DownloadError::BadSessionToken),
+        404 => Err(DownloadError::PuzzleLocked),
+        429 => Err(DownloadError::RateLimited),
+        _ => Err(DownloadError::UnexpectedStatus(status))
+    }
+}
+
+/// Checks that a response to an input download really holds an input. The site
+/// answers with an error message in the body for some failures, which would
+/// otherwise be cached as the input.
+pub fn check_input_response(status: u16, body: &str) -> Result<(), DownloadError> {
+    if body.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(DownloadError::PuzzleLocked);
+    }
+
+    if body.contains("Please log in") {
+        return Err(DownloadError::BadSessionToken);
     }
 
-    let session_token = std::fs::read(session_token_file)?;
-    let session_token = std::str::from_utf8(&session_token)?;
+    check_status(status)?;
 
-    let input_url = Url::parse(&format!("https://adventofcode.com/{}/day/{}/input", year_num, day_num))?;
-    let client = {
-        let cookie_store = Jar::default();
-        cookie_store.add_cookie_str(&format!("session={}", session_token), &input_url);
+    let start = body.trim_start();
+    if start.starts_with("<!DOCTYPE") || start.starts_with("<html") {
+        return Err(DownloadError::UnexpectedBody);
+    }
+
+    Ok(())
+}
 
-        ClientBuilder::new()
-            .cookie_provider(Arc::new(cookie_store))
-            .build()?
+/// Builds a client that sends the session token with every request to the
+/// Advent of Code site, see `config` for where the token comes from.
+pub fn build_client() -> Result<Client, DownloadError> {
+    let Some(session_token) = config().session_token()? else {
+        return Err(DownloadError::MissingSessionToken(config().session_token_file()));
     };
 
-    let response = client.get(input_url).send()?;
+    let base_url = get_base_url().map_err(|error| DownloadError::Request(error.to_string()))?;
+
+    let cookie_store = Jar::default();
+    cookie_store.add_cookie_str(&format!("session={}", session_token), &base_url);
+
+    Ok(ClientBuilder::new()
+        .cookie_provider(Arc::new(cookie_store))
+        .build()?)
+}
+
+fn download_input(aoc_year: &str, aoc_day: &str, path: &Path) -> Result<(), DownloadError> {
+    wait_for_unlock(&SystemClock, aoc_year, aoc_day, should_wait_for_unlock(), unlock_jitter())?;
+
+    let input_url = get_puzzle_url(aoc_year, aoc_day, "input").map_err(|error| DownloadError::Request(error.to_string()))?;
+    let response = build_client()?.get(input_url).send()?;
+    let status = response.status().as_u16();
+    let input = response.text()?;
+    check_input_response(status, &input)?;
 
     std::fs::create_dir_all(path.parent().unwrap())?;
-    std::fs::write(path, response.text()?)?;
+    std::fs::write(path, input)?;
 
     Ok(())
 }
 
-pub fn get_input(aoc_year: &str, aoc_day: &str, example_input: bool) -> String {
-    let mut path = std::env::current_dir().unwrap();
-    path.push("input");
+pub fn get_input_root() -> PathBuf {
+    config().input_root()
+}
+
+pub fn get_input_dir(aoc_year: &str, aoc_day: &str) -> PathBuf {
+    let mut path = get_input_root();
     path.push(aoc_year);
     path.push(aoc_day);
+
+    path
+}
+
+/// Directory of a named input of a day, e.g. a teammate's, which lives next to
+/// the default input as `input/<year>/<day>/<profile>/input.txt`.
+pub fn get_profile_dir(aoc_year: &str, aoc_day: &str, profile: Option<&str>) -> PathBuf {
+    let mut path = get_input_dir(aoc_year, aoc_day);
+    if let Some(profile) = profile {
+        path.push(profile);
+    }
+
+    path
+}
+
+/// Names of the input profiles of a day, sorted.
+pub fn get_input_profiles(aoc_year: &str, aoc_day: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(get_input_dir(aoc_year, aoc_day)) else {
+        return Vec::new();
+    };
+
+    let mut profiles =
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("input.txt").is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+
+    profiles.sort();
+    profiles
+}
+
+/// Reads the input of a profile. These belong to someone else so they're never
+/// downloaded.
+pub fn get_profile_input(aoc_year: &str, aoc_day: &str, profile: &str) -> String {
+    let path = get_profile_dir(aoc_year, aoc_day, Some(profile)).join("input.txt");
+    match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(error) => panic!("Failed to read input of profile {} at {:?}: {:?}", profile, path, error)
+    }
+}
+
+/// Reads an input from an arbitrary file, or from stdin if `path` is `-`.
+pub fn read_input(path: &Path) -> String {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        if let Err(error) = std::io::stdin().read_to_string(&mut input) {
+            panic!("Failed to read input from stdin: {:?}", error);
+        }
+
+        return input;
+    }
+
+    match std::fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(error) => panic!("Failed to read input file at {:?}: {:?}", path, error)
+    }
+}
+
+/// Reads the input of a day, downloading it (or extracting the example from the
+/// puzzle page) first if it isn't there yet.
+pub fn get_input(aoc_year: &str, aoc_day: &str, example_input: bool) -> Result<String, DownloadError> {
+    let mut path = get_input_dir(aoc_year, aoc_day);
     if example_input {
         path.push("example.txt");
 
@@ -234,46 +640,168 @@ pub fn get_input(aoc_year: &str, aoc_day: &str, example_input: bool) -> String {
         path.push("input.txt");
     }
 
-    if !example_input && !path.is_file() {
-        println!("Input not found for {}-{}, attempting to download it...", aoc_year, aoc_day);
-        if let Err(error) = download_input(aoc_year, aoc_day, &path) {
-            panic!("Failed to download input file: {:?}", error);
-        }
+    if example_input && !path.is_file() {
+        eprintln!("Example not found for {}-{}, attempting to extract it from the puzzle page...", aoc_year, aoc_day);
+        download_examples(aoc_year, aoc_day, path.parent().unwrap(), false)?;
+        eprintln!("Successfully downloaded example file, check it matches the puzzle");
 
-        println!("Successfully downloaded input file");
+    } else if !path.is_file() {
+        eprintln!("Input not found for {}-{}, attempting to download it...", aoc_year, aoc_day);
+        download_input(aoc_year, aoc_day, &path)?;
+        eprintln!("Successfully downloaded input file");
     }
 
-    if !path.is_file() {
+    Ok(std::fs::read_to_string(path)?)
+}
 
-        panic!("Failed to find input file at {:?}", path);
-    }
+#[macro_export]
+macro_rules! aoc_params {
+    ($year:ident, $day:ident) => {
+        &[]
+    };
+    ($year:ident, $day:ident, $params:ident) => {
+        $crate::$year::$day::$params
+    };
+}
+
+#[macro_export]
+macro_rules! aoc_day {
+    ($year:ident, $day:ident, ($($params:ident)?), [$($variant_part:literal $variant:ident $variant_fn:ident),*]) => {
+        $crate::scaffold::AocDay {
+            day: stringify!($day),
+            params: $crate::aoc_params!($year, $day $(, $params)?),
+            solution: $crate::scaffold::AocSolution::Parts {
+                part_1: {
+                    fn wrapper(input: $crate::scaffold::InputParser) -> Result<AocResult, $crate::error::AocError> {
+                        $crate::error::IntoAocResult::into_aoc_result($crate::$year::$day::part1(input.into()))
+                            .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some(1)))
+                    }
 
-    std::fs::read_to_string(path).unwrap()
+                    wrapper
+                },
+                part_2: {
+                    fn wrapper(input: $crate::scaffold::InputParser) -> Result<AocResult, $crate::error::AocError> {
+                        $crate::error::IntoAocResult::into_aoc_result($crate::$year::$day::part2(input.into()))
+                            .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some(2)))
+                    }
+
+                    wrapper
+                },
+                variants: &[
+                    $($crate::scaffold::AocVariant {
+                        name: stringify!($variant),
+                        part: $variant_part,
+                        solve: {
+                            fn wrapper(input: $crate::scaffold::InputParser) -> Result<AocResult, $crate::error::AocError> {
+                                $crate::error::IntoAocResult::into_aoc_result($crate::$year::$day::$variant_fn(input.into()))
+                                    .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some($variant_part)))
+                            }
+
+                            wrapper
+                        }
+                    }),*
+                ]
+            }
+        }
+    };
+    ($year:ident, $day:ident, $solver:ident, ($($params:ident)?), [$($variant_part:literal $variant:ident $variant_fn:ident),*]) => {
+        $crate::scaffold::AocDay {
+            day: stringify!($day),
+            params: $crate::aoc_params!($year, $day $(, $params)?),
+            solution: $crate::scaffold::AocSolution::Solver {
+                parse: {
+                    fn wrapper(input: $crate::scaffold::InputParser) -> Result<$crate::scaffold::ParsedInput, $crate::error::AocError> {
+                        match <$crate::$year::$day::$solver as $crate::scaffold::Solver>::parse(input) {
+                            Ok(parsed) => Ok(std::sync::Arc::new(parsed)),
+                            Err(error) => Err(error.with_context(stringify!($year), stringify!($day), None))
+                        }
+                    }
+
+                    wrapper
+                },
+                part_1: {
+                    fn wrapper(parsed: &$crate::scaffold::ParsedInput) -> Result<AocResult, $crate::error::AocError> {
+                        let parsed = parsed.downcast_ref().unwrap();
+                        $crate::error::IntoAocResult::into_aoc_result(<$crate::$year::$day::$solver as $crate::scaffold::Solver>::part1(parsed))
+                            .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some(1)))
+                    }
+
+                    wrapper
+                },
+                part_2: {
+                    fn wrapper(parsed: &$crate::scaffold::ParsedInput) -> Result<AocResult, $crate::error::AocError> {
+                        let parsed = parsed.downcast_ref().unwrap();
+                        $crate::error::IntoAocResult::into_aoc_result(<$crate::$year::$day::$solver as $crate::scaffold::Solver>::part2(parsed))
+                            .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some(2)))
+                    }
+
+                    wrapper
+                },
+                variants: &[
+                    $($crate::scaffold::AocVariant {
+                        name: stringify!($variant),
+                        part: $variant_part,
+                        solve: {
+                            fn wrapper(parsed: &$crate::scaffold::ParsedInput) -> Result<AocResult, $crate::error::AocError> {
+                                let parsed = parsed.downcast_ref().unwrap();
+                                $crate::error::IntoAocResult::into_aoc_result($crate::$year::$day::$solver::$variant_fn(parsed))
+                                    .map_err(|error| error.with_context(stringify!($year), stringify!($day), Some($variant_part)))
+                            }
+
+                            wrapper
+                        }
+                    }),*
+                ]
+            }
+        }
+    };
 }
 
+#[macro_export]
+macro_rules! aoc_bench {
+    ($year:ident, $day:ident, [$($variant_part:literal $variant:ident $variant_fn:ident),*]) => {
+        pub(crate) fn run_bench(c: &mut criterion::Criterion) {
+            let input = $crate::scaffold::get_input(stringify!($year), stringify!($day), false).unwrap_or_else(|error| panic!("Failed to get input: {}", error));
+
+            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_1"), |b| b.iter(|| $crate::$year::$day::part1($crate::scaffold::InputParser::new(&input).into())));
+            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_2"), |b| b.iter(|| $crate::$year::$day::part2($crate::scaffold::InputParser::new(&input).into())));
+            $(c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_", $variant_part, "::", stringify!($variant)), |b| b.iter(|| $crate::$year::$day::$variant_fn($crate::scaffold::InputParser::new(&input).into())));)*
+        }
+    };
+    ($year:ident, $day:ident, $solver:ident, [$($variant_part:literal $variant:ident $variant_fn:ident),*]) => {
+        pub(crate) fn run_bench(c: &mut criterion::Criterion) {
+            use $crate::scaffold::Solver;
+
+            let input = $crate::scaffold::get_input(stringify!($year), stringify!($day), false).unwrap_or_else(|error| panic!("Failed to get input: {}", error));
+            let parsed = $crate::$year::$day::$solver::parse($crate::scaffold::InputParser::new(&input)).unwrap();
+
+            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::parse"), |b| b.iter(|| $crate::$year::$day::$solver::parse($crate::scaffold::InputParser::new(&input))));
+            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_1"), |b| b.iter(|| $crate::$year::$day::$solver::part1(&parsed)));
+            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_2"), |b| b.iter(|| $crate::$year::$day::$solver::part2(&parsed)));
+            $(c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_", $variant_part, "::", stringify!($variant)), |b| b.iter(|| $crate::$year::$day::$solver::$variant_fn(&parsed)));)*
+        }
+    };
+}
+
+/// Registers the solvers of every day. A day is either a module with `part1`
+/// and `part2` functions, or `day: Type` for a module with a `Solver`,
+/// optionally followed by `(PARAMS)` if it declares puzzle parameters and by
+/// its variants as `[part name function, ...]`.
 #[macro_export]
 macro_rules! aoc_solvers {
     {
         $($year:ident {
-            $($day:ident),*
+            $($(#[$day_attr:meta])* $day:ident $(: $solver:ident)? $(($params:ident))? $([$($variant_part:literal $variant:ident $variant_fn:ident),*])?),*
         }),*
     } => {
 
         $(pub(crate) mod $year {
-            $(pub(crate) mod $day;)*
+            $($(#[$day_attr])* pub(crate) mod $day;)*
 
             pub(crate) mod bench {
                 $(
                     pub(crate) mod $day {
-
-                        use criterion::Criterion;
-
-                        pub(crate) fn run_bench(c: &mut Criterion) {
-                            let input = $crate::scaffold::get_input(stringify!($year), stringify!($day), false);
-
-                            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_1"), |b| b.iter(|| $crate::$year::$day::part1($crate::scaffold::InputParser::new(&input).into())));
-                            c.bench_function(concat!(stringify!($year), "::", stringify!($day), "::part_2"), |b| b.iter(|| $crate::$year::$day::part2($crate::scaffold::InputParser::new(&input).into())));
-                        }
+                        $crate::aoc_bench!($year, $day $(, $solver)?, [$($($variant_part $variant $variant_fn),*)?]);
                     }
                 )*
             }
@@ -289,25 +817,7 @@ macro_rules! aoc_solvers {
                     $crate::scaffold::AocYear {
                         year: stringify!($year),
                         days: &[
-                            $(
-                                $crate::scaffold::AocDay {
-                                    day: stringify!($day),
-                                    part_1: {
-                                        fn wrapper(input: $crate::scaffold::InputParser) -> AocResult {
-                                            $crate::scaffold::AocResult::from($crate::$year::$day::part1(input.into()))
-                                        }
-
-                                        wrapper
-                                    },
-                                    part_2: {
-                                        fn wrapper(input: $crate::scaffold::InputParser) -> AocResult {
-                                            $crate::scaffold::AocResult::from($crate::$year::$day::part2(input.into()))
-                                        }
-
-                                        wrapper
-                                    }
-                                }
-                            ),*
+                            $($crate::aoc_day!($year, $day $(, $solver)?, ($($params)?), [$($($variant_part $variant $variant_fn),*)?])),*
                         ]
                     }
                 ),*
@@ -319,3 +829,96 @@ macro_rules! aoc_solvers {
 
     };
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const NOT_UNLOCKED_BODY: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+    const LOGGED_OUT_BODY: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+    const PUZZLE_PAGE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/puzzle_page.html"));
+
+    fn error_position(input: &str, offset: usize) -> Option<(usize, usize)> {
+        let parser = InputParser { input_str: &input.as_bytes()[offset..], full_input: input.as_bytes(), params: &[] };
+        parser.error("unexpected input").position.map(|position| (position.line, position.column))
+    }
+
+    #[test]
+    fn points_errors_at_the_unparsed_input() {
+        let input = "12 34\n56 78\n\nab\n";
+        assert_eq!(error_position(input, 0), Some((1, 1)));
+        assert_eq!(error_position(input, 3), Some((1, 4)));
+        assert_eq!(error_position(input, 5), Some((1, 6)));
+        assert_eq!(error_position(input, 6), Some((2, 1)));
+        assert_eq!(error_position(input, 9), Some((2, 4)));
+        assert_eq!(error_position(input, 12), Some((3, 1)));
+        assert_eq!(error_position(input, 13), Some((4, 1)));
+        assert_eq!(error_position(input, 14), Some((4, 2)));
+        assert_eq!(error_position(input, input.len()), Some((5, 1)));
+    }
+
+    #[test]
+    fn points_errors_after_parsed_numbers() {
+        let mut parser = InputParser::new("1 2\n3 4\n5 x");
+        assert_eq!(parser.next_ints::<5>(), Some([1, 2, 3, 4, 5]));
+
+        let error = parser.error("expected a number");
+        assert_eq!(error.position.map(|position| (position.line, position.column)), Some((3, 2)));
+        assert_eq!(error.message, "expected a number");
+    }
+
+    #[test]
+    fn prints_nested_lists_with_brackets() {
+        assert_eq!(AocResult::from(vec![1u64, 2, 3]).to_string(), "1,2,3");
+        assert_eq!(AocResult::from(vec![vec![1u64, 2], vec![3]]).to_string(), "[1,2],[3]");
+        assert_eq!(AocResult::from(vec![vec![vec![1u64]], vec![]]).to_string(), "[[1]],[]");
+        assert_eq!(AocResult::from(Vec::<u64>::new()).to_string(), "");
+    }
+
+    #[test]
+    fn keeps_the_path_of_base_urls() {
+        let puzzle_url = |base_url: &str, page: &str| parse_base_url(base_url).unwrap().join(&puzzle_path("year_2022", "day_01", page)).unwrap().to_string();
+        assert_eq!(puzzle_url("https://adventofcode.com", ""), "https://adventofcode.com/2022/day/1");
+        assert_eq!(puzzle_url("https://adventofcode.com/", "input"), "https://adventofcode.com/2022/day/1/input");
+        assert_eq!(puzzle_url("http://localhost:8080/aoc", "answer"), "http://localhost:8080/aoc/2022/day/1/answer");
+        assert_eq!(puzzle_url("http://localhost:8080/aoc/", "answer"), "http://localhost:8080/aoc/2022/day/1/answer");
+        assert!(parse_base_url("not a url").is_err());
+    }
+
+    #[test]
+    fn maps_statuses_to_errors() {
+        assert!(check_status(200).is_ok());
+        assert!(check_status(204).is_ok());
+        assert!(matches!(check_status(400), Err(DownloadError::BadSessionToken)));
+        assert!(matches!(check_status(401), Err(DownloadError::BadSessionToken)));
+        assert!(matches!(check_status(403), Err(DownloadError::BadSessionToken)));
+        assert!(matches!(check_status(404), Err(DownloadError::PuzzleLocked)));
+        assert!(matches!(check_status(429), Err(DownloadError::RateLimited)));
+        assert!(matches!(check_status(500), Err(DownloadError::UnexpectedStatus(500))));
+        assert!(matches!(check_status(302), Err(DownloadError::UnexpectedStatus(302))));
+    }
+
+    #[test]
+    fn accepts_plain_inputs() {
+        assert!(check_input_response(200, "1000\n2000\n\n3000\n").is_ok());
+        assert!(check_input_response(200, "<<>>\n").is_ok());
+    }
+
+    #[test]
+    fn rejects_error_messages_in_input_bodies() {
+        assert!(matches!(check_input_response(404, NOT_UNLOCKED_BODY), Err(DownloadError::PuzzleLocked)));
+        assert!(matches!(check_input_response(200, NOT_UNLOCKED_BODY), Err(DownloadError::PuzzleLocked)));
+        assert!(matches!(check_input_response(400, LOGGED_OUT_BODY), Err(DownloadError::BadSessionToken)));
+        assert!(matches!(check_input_response(200, LOGGED_OUT_BODY), Err(DownloadError::BadSessionToken)));
+    }
+
+    #[test]
+    fn rejects_bad_statuses_and_html_bodies() {
+        assert!(matches!(check_input_response(429, ""), Err(DownloadError::RateLimited)));
+        assert!(matches!(check_input_response(500, "1 2 3\n"), Err(DownloadError::UnexpectedStatus(500))));
+        assert!(matches!(check_input_response(200, PUZZLE_PAGE), Err(DownloadError::UnexpectedBody)));
+        assert!(matches!(check_input_response(200, "\n<html><body>Oops</body></html>"), Err(DownloadError::UnexpectedBody)));
+    }
+
+}
@@ -0,0 +1,147 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::puzzle::strip_tags;
+use crate::scaffold::{build_client, get_puzzle_url};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitVerdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+    Unknown(String)
+}
+
+impl Display for SubmitVerdict {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitVerdict::Correct => write!(f, "RIGHT - that's the right answer"),
+            SubmitVerdict::Wrong => write!(f, "WRONG - that's not the right answer"),
+            SubmitVerdict::TooHigh => write!(f, "WRONG - the answer is too high"),
+            SubmitVerdict::TooLow => write!(f, "WRONG - the answer is too low"),
+            SubmitVerdict::RateLimited(Some(wait)) => write!(f, "RATE LIMITED - wait {:?} before submitting again", wait),
+            SubmitVerdict::RateLimited(None) => write!(f, "RATE LIMITED - wait before submitting again"),
+            SubmitVerdict::AlreadySolved => write!(f, "ALREADY SOLVED - this part was already completed"),
+            SubmitVerdict::Unknown(message) => write!(f, "UNKNOWN RESPONSE - {}", message)
+        }
+    }
+
+}
+
+/// Strips the tags from the `<article>` of a response page, which is where the
+/// site puts the message about the submitted answer.
+fn article_text(html: &str) -> String {
+    let article =
+        html.find("<article")
+            .map(|start| {
+                let end = html[start..].find("</article>").map_or(html.len(), |end| start + end);
+                &html[start..end]
+            })
+            .unwrap_or(html);
+
+    strip_tags(article).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses the wait time out of e.g. "You have 1m 23s left to wait."
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("You have ")? + "You have ".len();
+
+    let mut secs = 0;
+    for token in text[start..end].split_whitespace() {
+        let split_index = token.find(|c: char| !c.is_ascii_digit())?;
+        let (amount, unit) = token.split_at(split_index);
+        let amount = amount.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => amount * 60 * 60,
+            "m" => amount * 60,
+            "s" => amount,
+            _ => return None
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+pub fn parse_submit_response(html: &str) -> SubmitVerdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        SubmitVerdict::Correct
+
+    } else if text.contains("your answer is too high") {
+        SubmitVerdict::TooHigh
+
+    } else if text.contains("your answer is too low") {
+        SubmitVerdict::TooLow
+
+    } else if text.contains("That's not the right answer") {
+        SubmitVerdict::Wrong
+
+    } else if text.contains("You gave an answer too recently") {
+        SubmitVerdict::RateLimited(parse_wait_time(&text))
+
+    } else if text.contains("Did you already complete it?") {
+        SubmitVerdict::AlreadySolved
+
+    } else {
+        SubmitVerdict::Unknown(text)
+    }
+}
+
+pub fn submit_answer(aoc_year: &str, aoc_day: &str, part: usize, answer: &str) -> Result<SubmitVerdict, Box<dyn std::error::Error>> {
+    let answer_url = get_puzzle_url(aoc_year, aoc_day, "answer")?;
+    let level = part.to_string();
+    let response =
+        build_client()?
+            .post(answer_url)
+            .form(&[("level", level.as_str()), ("answer", answer)])
+            .send()?
+            .error_for_status()?;
+
+    Ok(parse_submit_response(&response.text()?))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    macro_rules! response_page {
+        ($name:literal) => {
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/submit/", $name, ".html"))
+        };
+    }
+
+    #[test]
+    fn parses_right_and_wrong_answers() {
+        assert_eq!(parse_submit_response(response_page!("right")), SubmitVerdict::Correct);
+        assert_eq!(parse_submit_response(response_page!("wrong")), SubmitVerdict::Wrong);
+        assert_eq!(parse_submit_response(response_page!("too_high")), SubmitVerdict::TooHigh);
+        assert_eq!(parse_submit_response(response_page!("too_low")), SubmitVerdict::TooLow);
+    }
+
+    #[test]
+    fn parses_rate_limits_and_solved_parts() {
+        assert_eq!(parse_submit_response(response_page!("rate_limited")), SubmitVerdict::RateLimited(Some(Duration::from_secs(83))));
+        assert_eq!(parse_submit_response(response_page!("already_solved")), SubmitVerdict::AlreadySolved);
+    }
+
+    #[test]
+    fn keeps_the_text_of_unknown_responses() {
+        let verdict = parse_submit_response("<html><article><p>Something   <em>else</em></p></article></html>");
+        assert_eq!(verdict, SubmitVerdict::Unknown("Something else".to_string()));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait_time("You have 1m 23s left to wait."), Some(Duration::from_secs(83)));
+        assert_eq!(parse_wait_time("You have 45s left to wait."), Some(Duration::from_secs(45)));
+        assert_eq!(parse_wait_time("You have 1h 2m 3s left to wait."), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_wait_time("You have a while left to wait."), None);
+        assert_eq!(parse_wait_time("Please wait one minute."), None);
+    }
+
+}
@@ -0,0 +1,178 @@
+use std::{io::Write, sync::atomic::{AtomicBool, Ordering}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use crate::scaffold::{DownloadError, day_number, year_number};
+
+// Puzzles unlock at midnight EST (UTC-5) on each day of December. Downloading
+// before that only gets an error page, so downloads wait for the unlock, as
+// long as it's close enough to be worth waiting for.
+const UNLOCK_HOUR_UTC: u64 = 5;
+const MAX_WAIT: Duration = Duration::from_secs(60 * 60);
+const MAX_JITTER_MS: u64 = 3000;
+
+static WAIT_FOR_UNLOCK: AtomicBool = AtomicBool::new(true);
+
+/// Source of the current time, so waiting for an unlock can be exercised
+/// without actually waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+}
+
+/// Converts a (year, month, day) date to days since the unix epoch, the
+/// inverse of `history::civil_from_days`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn unlock_time(year: i64, day: u32) -> SystemTime {
+    let days = days_from_civil(year, 12, day) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60 + UNLOCK_HOUR_UTC * 60 * 60)
+}
+
+pub fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}h {:02}m {:02}s", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Whether downloads should wait for puzzles to unlock, `--no-wait` turns this
+/// off.
+pub fn set_wait_for_unlock(wait: bool) {
+    WAIT_FOR_UNLOCK.store(wait, Ordering::Relaxed);
+}
+
+pub fn should_wait_for_unlock() -> bool {
+    WAIT_FOR_UNLOCK.load(Ordering::Relaxed)
+}
+
+/// How long to keep waiting after the unlock, random so everyone waiting
+/// doesn't hit the site in the same instant.
+pub fn unlock_jitter() -> Duration {
+    Duration::from_millis(std::random::random::<u64>(..) % MAX_JITTER_MS)
+}
+
+/// Waits until the puzzle is unlocked, printing a countdown, or fails if it
+/// isn't unlocked yet and `wait` is off or waiting would take too long. The
+/// wait ends `jitter` after the unlock.
+pub fn wait_for_unlock(clock: &impl Clock, aoc_year: &str, aoc_day: &str, wait: bool, jitter: Duration) -> Result<(), DownloadError> {
+    let (Ok(year), Ok(day)) = (year_number(aoc_year).parse::<i64>(), day_number(aoc_day).parse::<u32>()) else {
+        return Ok(());
+    };
+
+    let unlock = unlock_time(year, day);
+    let Ok(remaining) = unlock.duration_since(clock.now()) else {
+        return Ok(());
+    };
+
+    if !wait || remaining > MAX_WAIT {
+        return Err(DownloadError::NotUnlocked(remaining));
+    }
+
+    let mut stderr = std::io::stderr();
+    while let Ok(remaining) = (unlock + jitter).duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        let _ = write!(stderr, "\r{}-{} unlocks in {} ", aoc_year, aoc_day, format_countdown(remaining));
+        let _ = stderr.flush();
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    let _ = writeln!(stderr, "\r{}-{} is unlocked            ", aoc_year, aoc_day);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+
+    use super::*;
+
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        slept: Cell<Duration>
+    }
+
+    impl FakeClock {
+
+        fn at(now: SystemTime) -> Self {
+            FakeClock { now: Cell::new(now), slept: Cell::new(Duration::ZERO) }
+        }
+
+    }
+
+    impl Clock for FakeClock {
+
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.slept.set(self.slept.get() + duration);
+        }
+
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        assert_eq!(unlock_time(2022, 1), UNIX_EPOCH + Duration::from_secs(1669870800));
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "1h 02m 03s");
+    }
+
+    #[test]
+    fn does_not_wait_for_unlocked_puzzles() {
+        let clock = FakeClock::at(unlock_time(2022, 1) + Duration::from_secs(1));
+        assert!(wait_for_unlock(&clock, "year_2022", "day_01", false, Duration::from_secs(2)).is_ok());
+        assert_eq!(clock.slept.get(), Duration::ZERO);
+    }
+
+    #[test]
+    fn refuses_to_wait_with_no_wait() {
+        let clock = FakeClock::at(unlock_time(2022, 1) - Duration::from_secs(10));
+        let result = wait_for_unlock(&clock, "year_2022", "day_01", false, Duration::ZERO);
+        assert!(matches!(result, Err(DownloadError::NotUnlocked(remaining)) if remaining == Duration::from_secs(10)));
+        assert_eq!(clock.slept.get(), Duration::ZERO);
+    }
+
+    #[test]
+    fn refuses_to_wait_longer_than_max_wait() {
+        let clock = FakeClock::at(unlock_time(2022, 1) - MAX_WAIT - Duration::from_secs(1));
+        let result = wait_for_unlock(&clock, "year_2022", "day_01", true, Duration::ZERO);
+        assert!(matches!(result, Err(DownloadError::NotUnlocked(remaining)) if remaining > MAX_WAIT));
+        assert_eq!(clock.slept.get(), Duration::ZERO);
+    }
+
+    #[test]
+    fn counts_down_until_unlock_and_jitter() {
+        let unlock = unlock_time(2022, 1);
+        let jitter = Duration::from_millis(1500);
+        let clock = FakeClock::at(unlock - Duration::from_secs(90));
+        assert!(wait_for_unlock(&clock, "year_2022", "day_01", true, jitter).is_ok());
+        assert_eq!(clock.now(), unlock + jitter);
+        assert_eq!(clock.slept.get(), Duration::from_secs(90) + jitter);
+    }
+
+}
@@ -1,5 +1,6 @@
+use crate::scaffold::AocResult;
 
-pub fn part1(_input: &str) -> u64 {
+pub fn part1(_input: &str) -> AocResult {
     /*let (mut buffer, start_index) = {
         let bytes = input.trim().as_bytes();
         let mut buffer = Vec::with_capacity(bytes.len() + 20);
@@ -39,10 +40,10 @@ pub fn part1(_input: &str) -> u64 {
 
     val*/
 
-    u64::MAX
+    AocResult::Unsolved
 }
 
-pub fn part2(_input: &str) -> u64 {
+pub fn part2(_input: &str) -> AocResult {
     /*let (mut buffer, start_index) = {
         let bytes = input.trim().as_bytes();
         let mut buffer = Vec::with_capacity(bytes.len() + 20);
@@ -82,5 +83,5 @@ pub fn part2(_input: &str) -> u64 {
 
     val*/
 
-    u64::MAX
+    AocResult::Unsolved
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::scaffold::{AocParam, InputParser};
 use crate::utils::Vector2;
 use crate::utils::z3::*;
 
@@ -5,6 +6,12 @@ use fxhash::FxHashSet;
 
 use z3::{Config, Context, Optimize, SatResult, ast::Int};
 
+// The example uses `row = 10` and `bound = 20`, for answers 26 and 56000011.
+const ROW: AocParam = AocParam::new("row", 2000000);
+const UPPER_BOUND: AocParam = AocParam::new("bound", 4000000);
+
+pub const PARAMS: &[AocParam] = &[ROW, UPPER_BOUND];
+
 fn parse_int(bytes: &[u8], index: &mut usize) -> isize {
     let mut sign = 1;
     if bytes[*index] == b'-' {
@@ -71,15 +78,16 @@ fn get_ranges(sensors: &[(Vector2, Vector2)], y_index: isize) -> Vec<(isize, isi
     condensed_ranges
 }
 
-pub fn part1(input: &str) -> u64 {
+pub fn part1(input: InputParser) -> u64 {
+    let row = input.param(ROW);
     let sensors =
-        input_iterator(input)
+        input_iterator(input.into())
         .collect::<Vec<_>>();
 
     let mut total_count =
-        get_ranges(&sensors, 2000000)
+        get_ranges(&sensors, row)
             .into_iter()
-            .map(|(from, to)| (to - from) as u64)
+            .map(|(from, to)| (to - from + 1) as u64)
             .sum();
 
     let mut seen_b_pos = FxHashSet::default();
@@ -89,7 +97,7 @@ pub fn part1(input: &str) -> u64 {
         }
 
         seen_b_pos.insert(b_pos);
-        if b_pos.y() == 2000000 {
+        if b_pos.y() == row {
             total_count -= 1;
         }
     }
@@ -97,7 +105,61 @@ pub fn part1(input: &str) -> u64 {
     total_count
 }
 
-pub fn part2(input: &str) -> i64 {
+fn is_covered(sensors: &[(Vector2, isize)], position: Vector2) -> bool {
+    sensors
+        .iter()
+        .any(|&(s_pos, max_distance)| (position - s_pos).manhattan_distance() <= max_distance)
+}
+
+pub fn part2(input: InputParser) -> i64 {
+    let upper_bound = input.param(UPPER_BOUND);
+    let sensors =
+        input_iterator(input.into())
+        .map(|(s_pos, b_pos)| (s_pos, (b_pos - s_pos).manhattan_distance()))
+        .collect::<Vec<_>>();
+
+    // The beacon is the only position in range of no sensor, so it's just
+    // outside the range of some sensor: where the borders of sensor ranges on
+    // both diagonals cross, or where one meets the edge of the search area,
+    // or else in a corner.
+    let mut ascending = vec![];
+    let mut descending = vec![];
+    for &(s_pos, max_distance) in &sensors {
+        ascending.push(s_pos.y() - s_pos.x() + max_distance + 1);
+        ascending.push(s_pos.y() - s_pos.x() - max_distance - 1);
+        descending.push(s_pos.y() + s_pos.x() + max_distance + 1);
+        descending.push(s_pos.y() + s_pos.x() - max_distance - 1);
+    }
+
+    let corners = [(0, 0), (upper_bound, 0), (0, upper_bound), (upper_bound, upper_bound)].map(|(x, y)| Vector2::new(x, y));
+    let crossings =
+        ascending
+            .iter()
+            .flat_map(|&a| descending.iter().map(move |&d| (a, d)))
+            .filter(|(a, d)| (d - a) % 2 == 0)
+            .map(|(a, d)| Vector2::new((d - a) / 2, (d + a) / 2));
+
+    let edges =
+        ascending
+            .iter()
+            .flat_map(|&a| [(0, a), (upper_bound, upper_bound + a), (-a, 0), (upper_bound - a, upper_bound)])
+            .chain(descending.iter().flat_map(|&d| [(0, d), (upper_bound, d - upper_bound), (d, 0), (d - upper_bound, upper_bound)]))
+            .map(|(x, y)| Vector2::new(x, y));
+
+    let beacon =
+        corners
+            .into_iter()
+            .chain(crossings)
+            .chain(edges)
+            .filter(|position| (0..=upper_bound).contains(&position.x()) && (0..=upper_bound).contains(&position.y()))
+            .find(|&position| !is_covered(&sensors, position))
+            .unwrap();
+
+    beacon.x() as i64 * 4000000 + beacon.y() as i64
+}
+
+pub fn part2_z3(input: InputParser) -> i64 {
+    let upper_bound = input.param(UPPER_BOUND);
     let config = Config::new();
     let context = Context::new(&config);
     let solver = Optimize::new(&context);
@@ -105,7 +167,7 @@ pub fn part2(input: &str) -> i64 {
     let zero = Int::from_i64(&context, 0);
     let s_x = Int::new_const(&context, "s_x");
     let s_y = Int::new_const(&context, "s_y");
-    for (s_pos, b_pos) in input_iterator(input) {
+    for (s_pos, b_pos) in input_iterator(input.into()) {
         let zs_x = Int::from_i64(&context, s_pos.x() as i64);
         let zs_y = Int::from_i64(&context, s_pos.y() as i64);
 
@@ -119,7 +181,7 @@ pub fn part2(input: &str) -> i64 {
         solver.assert(&abs_zsd.gt(&zd));
     }
 
-    let upper_bound = Int::from_i64(&context, 4000000);
+    let upper_bound = Int::from_i64(&context, upper_bound as i64);
     solver.assert(&s_x.ge(&zero));
     solver.assert(&s_x.le(&upper_bound));
     solver.assert(&s_y.ge(&zero));
@@ -132,3 +194,56 @@ pub fn part2(input: &str) -> i64 {
 
     x * 4000000 + y
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n",
+        "Sensor at x=9, y=16: closest beacon is at x=10, y=16\n",
+        "Sensor at x=13, y=2: closest beacon is at x=15, y=3\n",
+        "Sensor at x=12, y=14: closest beacon is at x=10, y=16\n",
+        "Sensor at x=10, y=20: closest beacon is at x=10, y=16\n",
+        "Sensor at x=14, y=17: closest beacon is at x=10, y=16\n",
+        "Sensor at x=8, y=7: closest beacon is at x=2, y=10\n",
+        "Sensor at x=2, y=0: closest beacon is at x=2, y=10\n",
+        "Sensor at x=0, y=11: closest beacon is at x=2, y=10\n",
+        "Sensor at x=20, y=14: closest beacon is at x=25, y=17\n",
+        "Sensor at x=17, y=20: closest beacon is at x=21, y=22\n",
+        "Sensor at x=16, y=7: closest beacon is at x=15, y=3\n",
+        "Sensor at x=14, y=3: closest beacon is at x=15, y=3\n",
+        "Sensor at x=20, y=1: closest beacon is at x=15, y=3\n");
+
+    fn example_params() -> Vec<(String, isize)> {
+        vec![("row".to_string(), 10), ("bound".to_string(), 20)]
+    }
+
+    #[test]
+    fn part1_example() {
+        let params = example_params();
+        assert_eq!(part1(InputParser::with_params(EXAMPLE, &params)), 26);
+    }
+
+    #[test]
+    fn part2_example() {
+        let params = example_params();
+        assert_eq!(part2(InputParser::with_params(EXAMPLE, &params)), 56000011);
+    }
+
+    #[test]
+    fn part2_beacon_on_an_edge() {
+        let input = concat!(
+            "Sensor at x=21, y=16: closest beacon is at x=15, y=3\n",
+            "Sensor at x=19, y=3: closest beacon is at x=31, y=3\n",
+            "Sensor at x=22, y=-3: closest beacon is at x=30, y=-11\n",
+            "Sensor at x=2, y=21: closest beacon is at x=-9, y=6\n",
+            "Sensor at x=0, y=24: closest beacon is at x=13, y=17\n",
+            "Sensor at x=2, y=22: closest beacon is at x=-1, y=18\n");
+
+        let params = example_params();
+        assert_eq!(part2(InputParser::with_params(input, &params)), 32000000);
+    }
+
+}
@@ -1,6 +1,9 @@
 
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
+use crate::error::AocError;
+use crate::scaffold::{InputParser, Solver};
+
 fn div_ceil(num: u16, div: u16) -> u16 {
     let mut val = num / div;
     if (num % div) != 0 {
@@ -252,7 +255,7 @@ impl State {
 
 }
 
-struct Blueprint {
+pub struct Blueprint {
     ore_rb_cost: Resources,
     clay_rb_cost: Resources,
     obs_rb_cost: Resources,
@@ -322,21 +325,35 @@ impl Blueprint {
 
 }
 
-pub fn part1(input: &str) -> u64 {
-    input
-    .lines()
-    .map(|line| Blueprint::parse_blueprint(line))
-    .map(|bp| bp.simulate_blueprint::<24>())
-    .enumerate()
-    .map(|(index, geodes)| ((index as u64) + 1) * geodes)
-    .sum()
-}
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Vec<Blueprint>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: InputParser) -> Result<Self::Parsed, AocError> {
+        Ok(<&str>::from(input)
+            .lines()
+            .map(Blueprint::parse_blueprint)
+            .collect())
+    }
 
-pub fn part2(input: &str) -> u64 {
-    input
-    .lines()
-    .take(3)
-    .map(|line| Blueprint::parse_blueprint(line))
-    .map(|bp| bp.simulate_blueprint::<32>())
-    .fold(1, |p, geodes| p * geodes)
-}
\ No newline at end of file
+    fn part1(blueprints: &Self::Parsed) -> Self::Part1 {
+        blueprints
+        .iter()
+        .map(|bp| bp.simulate_blueprint::<24>())
+        .enumerate()
+        .map(|(index, geodes)| ((index as u64) + 1) * geodes)
+        .sum()
+    }
+
+    fn part2(blueprints: &Self::Parsed) -> Self::Part2 {
+        blueprints
+        .iter()
+        .take(3)
+        .map(|bp| bp.simulate_blueprint::<32>())
+        .fold(1, |p, geodes| p * geodes)
+    }
+
+}
@@ -62,7 +62,50 @@ fn generate_ast<'a>(context: &'a Context, var_name: &'a str, expr_map: &FxHashMa
     ast_cache.get(var_name).unwrap().clone()
 }
 
-pub fn part1(input: &str) -> i64 {
+fn depends_on_humn(var_name: &str, expr_map: &FxHashMap<&str, (&str, u8, &str)>) -> bool {
+    match expr_map.get(var_name) {
+        Some(&(dep_1, _, dep_2)) => depends_on_humn(dep_1, expr_map) || depends_on_humn(dep_2, expr_map),
+        None => var_name == "humn"
+    }
+}
+
+/// Finds the value of `humn` that makes `var_name` evaluate to `target`, by
+/// undoing the operations on the path from `var_name` down to `humn`.
+fn solve_for_humn<'a>(var_name: &'a str, target: i64, expr_map: &FxHashMap<&str, (&'a str, u8, &'a str)>, expr_cache: &mut FxHashMap<&'a str, i64>) -> i64 {
+    if var_name == "humn" {
+        return target;
+    }
+
+    let &(dep_1, op, dep_2) = expr_map.get(var_name).unwrap();
+    if depends_on_humn(dep_1, expr_map) {
+        let dep_2_val = eval_expression(dep_2, expr_map, expr_cache);
+        let dep_1_target = match op {
+            b'+' => target - dep_2_val,
+            b'-' => target + dep_2_val,
+            b'/' => target * dep_2_val,
+            b'*' => target / dep_2_val,
+            op => panic!("Bad op {}", op)
+        };
+
+        solve_for_humn(dep_1, dep_1_target, expr_map, expr_cache)
+
+    } else {
+        let dep_1_val = eval_expression(dep_1, expr_map, expr_cache);
+        let dep_2_target = match op {
+            b'+' => target - dep_1_val,
+            b'-' => dep_1_val - target,
+            b'/' => dep_1_val / target,
+            b'*' => target / dep_1_val,
+            op => panic!("Bad op {}", op)
+        };
+
+        solve_for_humn(dep_2, dep_2_target, expr_map, expr_cache)
+    }
+}
+
+type Expressions<'a> = FxHashMap<&'a str, (&'a str, u8, &'a str)>;
+
+fn parse_expressions(input: &str) -> (Expressions<'_>, FxHashMap<&str, i64>) {
     let mut expr_map = FxHashMap::default();
     let mut expr_cache = FxHashMap::default();
     for line in input.lines() {
@@ -80,10 +123,28 @@ pub fn part1(input: &str) -> i64 {
         }
     }
 
+    (expr_map, expr_cache)
+}
+
+pub fn part1(input: &str) -> i64 {
+    let (expr_map, mut expr_cache) = parse_expressions(input);
     eval_expression("root", &expr_map, &mut expr_cache)
 }
 
 pub fn part2(input: &str) -> i64 {
+    let (expr_map, mut expr_cache) = parse_expressions(input);
+    let &(dep_1, _, dep_2) = expr_map.get("root").unwrap();
+    if depends_on_humn(dep_1, &expr_map) {
+        let target = eval_expression(dep_2, &expr_map, &mut expr_cache);
+        solve_for_humn(dep_1, target, &expr_map, &mut expr_cache)
+
+    } else {
+        let target = eval_expression(dep_1, &expr_map, &mut expr_cache);
+        solve_for_humn(dep_2, target, &expr_map, &mut expr_cache)
+    }
+}
+
+pub fn part2_z3(input: &str) -> i64 {
     let config = Config::new();
     let context = Context::new(&config);
     let solver = Optimize::new(&context);
@@ -1,3 +1,5 @@
+use crate::scaffold::AocResult;
+
 fn from_snafu(s: &str) -> i64 {
     let mut val = 0;
     let mut position = 1;
@@ -56,6 +58,6 @@ pub fn part1(input: &str) -> String {
         .sum())
 }
 
-pub fn part2(_: &str) -> u64 {
-    0
+pub fn part2(_: &str) -> AocResult {
+    AocResult::NotApplicable
 }
@@ -1,20 +1,26 @@
 
 use std::collections::VecDeque;
 
-use crate::{scaffold::InputParser, utils::{Matrix2DOwned, Vector2}};
+use crate::{scaffold::{AocParam, InputParser}, utils::{Matrix2DOwned, Vector2}};
 
-const GRID_SIZE: usize = 71;
+const GRID_SIZE: AocParam = AocParam::new("size", 71);
+const FALLEN_BYTES: AocParam = AocParam::new("bytes", 1024);
 const LEFT: Vector2 = Vector2::new(-1, 0);
 const UP: Vector2 = Vector2::new(0, -1);
 
+pub const PARAMS: &[AocParam] = &[GRID_SIZE, FALLEN_BYTES];
+
 pub fn part1(mut input: InputParser) -> u64 {
+    let grid_size = input.param(GRID_SIZE) as usize;
+    let fallen_bytes = input.param(FALLEN_BYTES) as usize;
+
     let mut bytes = Vec::new();
     while let Some(pair) = input.next_ints::<2>() {
         bytes.push(Vector2::new(pair[0], pair[1]));
     }
 
-    let mut grid = Matrix2DOwned::<bool>::new(GRID_SIZE, GRID_SIZE);
-    for index in 0..1024.min(bytes.len()) {
+    let mut grid = Matrix2DOwned::<bool>::new(grid_size, grid_size);
+    for index in 0..fallen_bytes.min(bytes.len()) {
         grid[bytes[index]] = true;
     }
 
@@ -108,17 +114,19 @@ impl UnionFindGrid {
 }
 
 pub fn part2(mut input: InputParser) -> Vector2 {
+    let grid_size = input.param(GRID_SIZE) as usize;
+
     let mut corrupted_bytes = Vec::new();
-    let mut corrupted_bytes_grid = Matrix2DOwned::new(GRID_SIZE, GRID_SIZE);
+    let mut corrupted_bytes_grid = Matrix2DOwned::new(grid_size, grid_size);
     while let Some(pair) = input.next_ints::<2>() {
         let point = Vector2::new(pair[0], pair[1]);
         corrupted_bytes.push(point);
         corrupted_bytes_grid[point] = true;
     }
 
-    let mut grid = UnionFindGrid::new(GRID_SIZE, GRID_SIZE);
-    for r in 0..GRID_SIZE {
-        for c in 0..GRID_SIZE {
+    let mut grid = UnionFindGrid::new(grid_size, grid_size);
+    for r in 0..grid_size {
+        for c in 0..grid_size {
             let grid_point = Vector2::new(c as isize, r as isize);
             if corrupted_bytes_grid[grid_point] {
                 continue;
@@ -141,7 +149,7 @@ pub fn part2(mut input: InputParser) -> Vector2 {
     }
 
     let start_point = Vector2::new(0, 0);
-    let end_point = Vector2::new((GRID_SIZE - 1) as isize, (GRID_SIZE - 1) as isize);
+    let end_point = Vector2::new((grid_size - 1) as isize, (grid_size - 1) as isize);
 
     assert_ne!(grid.get_root(start_point), grid.get_root(end_point));
 
@@ -1,6 +1,8 @@
 
 use fxhash::FxHashMap;
 
+use crate::error::AocError;
+
 #[derive(Default)]
 struct Graph<'a> {
     node_edges: Vec<Vec<usize>>,
@@ -30,18 +32,18 @@ impl<'a> Graph<'a> {
         }
     }
 
-    pub fn from_input(input: &'a str) -> Self {
+    pub fn from_input(input: &'a str) -> Result<Self, AocError> {
         let mut graph = Graph::default();
-        for line in input.lines() {
-            let mut parts = line.split("-");
-            let left = parts.next().unwrap();
-            let right = parts.next().unwrap();
+        for (index, line) in input.lines().enumerate() {
+            let Some((left, right)) = line.split_once("-") else {
+                return Err(AocError::new(format!("expected a connection like `ab-cd`, found {:?}", line)).at(index + 1, 1));
+            };
     
             graph.add_undirected_edge(left, right);
         }
     
         graph.finalize_graph();
-        graph
+        Ok(graph)
     }
 
     pub fn get_three_clique_count(&self) -> u64 {
@@ -134,10 +136,10 @@ impl<'a> Graph<'a> {
 
 }
 
-pub fn part1(input: &str) -> u64 {
-    Graph::from_input(input).get_three_clique_count()
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    Ok(Graph::from_input(input)?.get_three_clique_count())
 }
 
-pub fn part2(input: &str) -> String {
-    Graph::from_input(input).get_maximum_clique()
+pub fn part2(input: &str) -> Result<String, AocError> {
+    Ok(Graph::from_input(input)?.get_maximum_clique())
 }
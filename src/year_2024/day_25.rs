@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use crate::scaffold::AocResult;
 
 
 pub fn part1(input: &str) -> u64 {
@@ -70,6 +71,6 @@ pub fn part1(input: &str) -> u64 {
     count
 }
 
-pub fn part2(_input: &str) -> u64 {
-    0
+pub fn part2(_input: &str) -> AocResult {
+    AocResult::NotApplicable
 }